@@ -1,12 +1,15 @@
-use reqwest::{header, Client, StatusCode};
+use reqwest::{header, Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use std::time::Duration;
 use url::Url;
 
 use crate::models::{CollectionsPage, MediaPage, Photo, PhotosPage, Video, VideosPage};
+use crate::page_url::{self, Paginated};
+use crate::schema::{self, UnknownFields};
 use crate::search::{
     CollectionMediaParams, PaginationParams, PopularVideoParams, SearchParams, VideoSearchParams,
 };
-use crate::PexelsError;
+use crate::{CollectionId, PexelsError, PhotoId, VideoId};
 
 /// Main client for the Pexels API
 ///
@@ -21,6 +24,9 @@ pub struct PexelsClient {
 
     /// Base URL for the Pexels API
     base_url: String,
+
+    /// Whether responses with fields unknown to the models are rejected
+    strict: bool,
 }
 
 impl PexelsClient {
@@ -48,7 +54,12 @@ impl PexelsClient {
             .build()
             .unwrap_or_default();
 
-        Self { api_key: api_key.into(), client, base_url: "https://api.pexels.com/v1".to_string() }
+        Self {
+            api_key: api_key.into(),
+            client,
+            base_url: "https://api.pexels.com/v1".to_string(),
+            strict: false,
+        }
     }
 
     /// Creates a new PexelsClient with custom configuration
@@ -73,7 +84,12 @@ impl PexelsClient {
             .build()
             .unwrap_or_default();
 
-        Self { api_key: api_key.into(), client, base_url: "https://api.pexels.com/v1".to_string() }
+        Self {
+            api_key: api_key.into(),
+            client,
+            base_url: "https://api.pexels.com/v1".to_string(),
+            strict: false,
+        }
     }
 
     /// Sets a custom base URL for the Pexels API
@@ -90,6 +106,24 @@ impl PexelsClient {
         self
     }
 
+    /// Rejects responses with fields unknown to the models
+    ///
+    /// Unknown fields are normally kept in the `extra` map of each model. In strict mode
+    /// the request fails with [`PexelsError::SchemaDrift`] listing their paths instead,
+    /// which is useful in tests to notice changes of the API.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether strict mode is enabled
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Search for photos matching the specified query and parameters
     ///
     /// # Arguments
@@ -138,7 +172,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let photos_page: PhotosPage = response.json().await?;
+                let photos_page: PhotosPage = self.parse(response).await?;
                 Ok(photos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -170,7 +204,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let photos_page: PhotosPage = response.json().await?;
+                let photos_page: PhotosPage = self.parse(response).await?;
                 Ok(photos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -190,14 +224,15 @@ impl PexelsClient {
     /// # Returns
     ///
     /// A Result containing the photo or an error
-    pub async fn get_photo(&self, id: u64) -> Result<Photo, PexelsError> {
+    pub async fn get_photo(&self, id: impl Into<PhotoId>) -> Result<Photo, PexelsError> {
+        let id = id.into();
         let url = Url::parse(&format!("{}/photos/{}", self.base_url, id))?;
 
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let photo: Photo = response.json().await?;
+                let photo: Photo = self.parse(response).await?;
                 Ok(photo)
             }
             StatusCode::NOT_FOUND => {
@@ -235,7 +270,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let videos_page: VideosPage = response.json().await?;
+                let videos_page: VideosPage = self.parse(response).await?;
                 Ok(videos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -284,7 +319,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let videos_page: VideosPage = response.json().await?;
+                let videos_page: VideosPage = self.parse(response).await?;
                 Ok(videos_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -304,14 +339,15 @@ impl PexelsClient {
     /// # Returns
     ///
     /// A Result containing the video or an error
-    pub async fn get_video(&self, id: u64) -> Result<Video, PexelsError> {
+    pub async fn get_video(&self, id: impl Into<VideoId>) -> Result<Video, PexelsError> {
+        let id = id.into();
         let url = Url::parse(&format!("{}/videos/videos/{}", self.base_url, id))?;
 
         let response = self.send_request(url).await?;
 
         match response.status() {
             StatusCode::OK => {
-                let video: Video = response.json().await?;
+                let video: Video = self.parse(response).await?;
                 Ok(video)
             }
             StatusCode::NOT_FOUND => {
@@ -344,7 +380,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let collections_page: CollectionsPage = response.json().await?;
+                let collections_page: CollectionsPage = self.parse(response).await?;
                 Ok(collections_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -376,7 +412,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let collections_page: CollectionsPage = response.json().await?;
+                let collections_page: CollectionsPage = self.parse(response).await?;
                 Ok(collections_page)
             }
             StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
@@ -399,7 +435,7 @@ impl PexelsClient {
     /// A Result containing the media response or an error
     pub async fn get_collection_media(
         &self,
-        id: &str,
+        id: impl Into<CollectionId>,
         params: &PaginationParams,
     ) -> Result<MediaPage, PexelsError> {
         let params = CollectionMediaParams::from_pagination(params);
@@ -418,9 +454,10 @@ impl PexelsClient {
     /// A Result containing the media response or an error
     pub async fn get_collection_media_with_params(
         &self,
-        id: &str,
+        id: impl Into<CollectionId>,
         params: &CollectionMediaParams,
     ) -> Result<MediaPage, PexelsError> {
+        let id = id.into();
         let mut url = Url::parse(&format!("{}/collections/{}", self.base_url, id))?;
 
         self.append_query_params(&mut url, params.to_query_params());
@@ -429,7 +466,7 @@ impl PexelsClient {
 
         match response.status() {
             StatusCode::OK => {
-                let media_page: MediaPage = response.json().await?;
+                let media_page: MediaPage = self.parse(response).await?;
                 Ok(media_page)
             }
             StatusCode::NOT_FOUND => {
@@ -443,6 +480,73 @@ impl PexelsClient {
         }
     }
 
+    /// Fetches the page at `url`, e.g. a `next_page` link persisted as a cursor
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The page URL, which must have the same origin as the base URL
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the page or an error. URLs of other hosts fail with
+    /// [`PexelsError::InvalidPageUrl`], so the API key is never sent elsewhere.
+    pub async fn fetch_page<T: DeserializeOwned + UnknownFields>(
+        &self,
+        url: &str,
+    ) -> Result<T, PexelsError> {
+        let url = page_url::same_origin(url, &self.base_url)?;
+
+        let response = self.send_request(url.clone()).await?;
+
+        self.parse(page_url::check_status(response, &url)?).await
+    }
+
+    /// Fetches the page after `page`
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the next page, `None` if `page` is the last one, or an error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use pexels_api::{PexelsClient, SearchParams};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = PexelsClient::new("your_api_key");
+    ///     let mut page = client.search_photos("nature", &SearchParams::new()).await?;
+    ///     while let Some(next) = client.fetch_next(&page).await? {
+    ///         page = next;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn fetch_next<T>(&self, page: &T) -> Result<Option<T>, PexelsError>
+    where
+        T: Paginated + DeserializeOwned + UnknownFields,
+    {
+        match page.next_page_url() {
+            Some(url) => self.fetch_page(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches the page before `page`
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the previous page, `None` if `page` is the first one, or an error
+    pub async fn fetch_prev<T>(&self, page: &T) -> Result<Option<T>, PexelsError>
+    where
+        T: Paginated + DeserializeOwned + UnknownFields,
+    {
+        match page.prev_page_url() {
+            Some(url) => self.fetch_page(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
     fn append_query_params(&self, url: &mut Url, params: Vec<(String, String)>) {
         for (key, value) in params {
             url.query_pairs_mut().append_pair(&key, &value);
@@ -458,10 +562,58 @@ impl PexelsClient {
     /// # Returns
     ///
     /// A Result containing the HTTP response or an error
-    async fn send_request(&self, url: Url) -> Result<reqwest::Response, PexelsError> {
+    async fn send_request(&self, url: Url) -> Result<Response, PexelsError> {
         let response =
             self.client.get(url).header(header::AUTHORIZATION, &self.api_key).send().await?;
 
         Ok(response)
     }
+
+    /// Parses a successful response, checking for unknown fields in strict mode
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The HTTP response
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the parsed model or an error
+    async fn parse<T: DeserializeOwned + UnknownFields>(
+        &self,
+        response: Response,
+    ) -> Result<T, PexelsError> {
+        schema::check(response.json().await?, self.strict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{json_response, serve_base_url};
+    use serde_json::json;
+
+    fn page(page: u32, prev_page: Option<String>, next_page: Option<String>) -> serde_json::Value {
+        json!({
+            "page": page, "per_page": 1, "total_results": 2, "photos": [],
+            "prev_page": prev_page, "next_page": next_page,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_next_and_prev() {
+        let second = page(2, Some("http://127.0.0.1:1/v1/curated/?page=1".to_string()), None);
+        let base_url = format!("{}/v1", serve_base_url(vec![json_response(&second)]).await);
+        let client = PexelsClient::new("key").with_base_url(&base_url);
+
+        let first: PhotosPage =
+            serde_json::from_value(page(1, None, Some(format!("{base_url}/curated/?page=2"))))
+                .unwrap();
+        assert_eq!(client.fetch_prev(&first).await.unwrap().map(|p| p.page), None);
+        let second = client.fetch_next(&first).await.unwrap().unwrap();
+        assert_eq!(second.page, 2);
+        assert!(client.fetch_next(&second).await.unwrap().is_none());
+
+        // The previous page is on another port, so the API key must not be sent there
+        assert!(matches!(client.fetch_prev(&second).await, Err(PexelsError::InvalidPageUrl(_))));
+    }
 }
@@ -1,5 +1,7 @@
+use crate::page_url::{Endpoint, PageUrl};
 use crate::{
-    CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
+    validate, CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH,
+    PEXELS_VERSION,
 };
 use url::Url;
 
@@ -18,6 +20,13 @@ impl Featured {
         FeaturedBuilder::default()
     }
 
+    /// Parses a featured collections URL, such as the `next_page` of a [`CollectionsResponse`],
+    /// back into a `Featured` request.
+    pub fn from_url(url: &str) -> Result<Featured, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::FeaturedCollections)?;
+        Ok(Featured { page: url.get("page")?, per_page: url.get("per_page")? })
+    }
+
     /// Constructs the URI for the featured collections request based on the [`FeaturedBuilder`] builder's parameters.
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!(
@@ -41,7 +50,7 @@ impl Featured {
     pub async fn fetch(&self, client: &Pexels) -> Result<CollectionsResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let collection_response: CollectionsResponse = serde_json::from_value(response)?;
+        let collection_response: CollectionsResponse = client.parse(response)?;
         Ok(collection_response)
     }
 }
@@ -75,4 +84,14 @@ impl FeaturedBuilder {
     pub fn build(self) -> Featured {
         Featured { page: self.page, per_page: self.per_page }
     }
+
+    /// Builds `Featured` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Featured, PexelsError> {
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
 }
@@ -1,5 +1,7 @@
+use crate::page_url::{Endpoint, PageUrl};
 use crate::{
-    CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
+    validate, CollectionsResponse, Pexels, PexelsError, PEXELS_API, PEXELS_COLLECTIONS_PATH,
+    PEXELS_VERSION,
 };
 use url::Url;
 
@@ -15,6 +17,13 @@ impl Collections {
         CollectionsBuilder::default()
     }
 
+    /// Parses a collections URL, such as the `next_page` of a [`CollectionsResponse`], back
+    /// into a `Collections` request.
+    pub fn from_url(url: &str) -> Result<Collections, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::Collections)?;
+        Ok(Collections { page: url.get("page")?, per_page: url.get("per_page")? })
+    }
+
     /// Constructs the URI for the collections request based on the builder's parameters.
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!("{PEXELS_API}/{PEXELS_VERSION}/{PEXELS_COLLECTIONS_PATH}");
@@ -36,7 +45,7 @@ impl Collections {
     pub async fn fetch(&self, client: &Pexels) -> Result<CollectionsResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let collections_response: CollectionsResponse = serde_json::from_value(response)?;
+        let collections_response: CollectionsResponse = client.parse(response)?;
         Ok(collections_response)
     }
 }
@@ -70,4 +79,14 @@ impl CollectionsBuilder {
     pub fn build(self) -> Collections {
         Collections { page: self.page, per_page: self.per_page }
     }
+
+    /// Builds `Collections` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Collections, PexelsError> {
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
 }
@@ -1,6 +1,7 @@
+use crate::page_url::PageUrl;
 use crate::{
-    MediaResponse, MediaSort, MediaType as LibType, Pexels, PexelsError, PEXELS_API,
-    PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
+    validate, CollectionId, MediaResponse, MediaSort, MediaType as LibType, Pexels, PexelsError,
+    PEXELS_API, PEXELS_COLLECTIONS_PATH, PEXELS_VERSION,
 };
 use url::Url;
 
@@ -8,7 +9,7 @@ use url::Url;
 /// This endpoint returns all media items (photos and videos) within a single collection.
 /// Use the `type` parameter to filter results to only photos or only videos.
 pub struct Media {
-    id: String,
+    id: CollectionId,
     r#type: Option<LibType>,
     sort: Option<MediaSort>,
     page: Option<usize>,
@@ -21,6 +22,19 @@ impl Media {
         MediaBuilder::new()
     }
 
+    /// Parses a collection media URL, such as the `next_page` of a [`MediaResponse`], back
+    /// into a `Media` request.
+    pub fn from_url(url: &str) -> Result<Media, PexelsError> {
+        let url = PageUrl::parse(url)?;
+        Ok(Media {
+            id: url.collection_id()?,
+            r#type: url.get("type")?,
+            sort: url.get("sort")?,
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+        })
+    }
+
     /// Constructs the URI for the media request based on the builder's parameters.
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri =
@@ -56,7 +70,7 @@ impl Media {
     pub async fn fetch(&self, client: &Pexels) -> Result<MediaResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let media_response: MediaResponse = serde_json::from_value(response)?;
+        let media_response: MediaResponse = client.parse(response)?;
         Ok(media_response)
     }
 }
@@ -64,7 +78,7 @@ impl Media {
 /// Builder for constructing a `Media` request.
 #[derive(Default)]
 pub struct MediaBuilder {
-    id: String,
+    id: CollectionId,
     r#type: Option<LibType>,
     sort: Option<MediaSort>,
     page: Option<usize>,
@@ -74,12 +88,12 @@ pub struct MediaBuilder {
 impl MediaBuilder {
     /// Creates a new `MediaBuilder`.
     pub fn new() -> Self {
-        Self { id: "".to_string(), r#type: None, sort: None, page: None, per_page: None }
+        Self { id: CollectionId::default(), r#type: None, sort: None, page: None, per_page: None }
     }
 
-    /// Sets the ID of the media item to be fetched.
-    pub fn id(mut self, id: String) -> Self {
-        self.id = id;
+    /// Sets the ID of the collection whose media is fetched.
+    pub fn id(mut self, id: impl Into<CollectionId>) -> Self {
+        self.id = id.into();
         self
     }
 
@@ -117,15 +131,51 @@ impl MediaBuilder {
             per_page: self.per_page,
         }
     }
+
+    /// Builds `Media` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if the collection id is empty, `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Media, PexelsError> {
+        validate::not_blank("id", self.id.as_str())?;
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_url() {
+        let media = Media::from_url(
+            "https://api.pexels.com/v1/collections/8xntbhr/?page=2&per_page=10&type=photos",
+        );
+        assert_eq!(
+            media.unwrap().create_uri().unwrap(),
+            "https://api.pexels.com/v1/collections/8xntbhr?type=photos&page=2&per_page=10"
+        );
+        assert!(Media::from_url("https://api.pexels.com/v1/collections/featured").is_err());
+    }
+
     #[test]
     fn test_id() {
         let uri = MediaBuilder::new().id("123".to_string()).build();
         assert_eq!("https://api.pexels.com/v1/collections/123", uri.create_uri().unwrap());
     }
+
+    #[test]
+    fn test_try_build() {
+        assert_eq!(
+            MediaBuilder::new().try_build().err(),
+            Some(PexelsError::InvalidParameter("id must not be empty".to_string()))
+        );
+        assert_eq!(
+            MediaBuilder::new().id("abc").page(0).try_build().err(),
+            Some(PexelsError::InvalidParameter("page must be at least 1, got 0".to_string()))
+        );
+        assert!(MediaBuilder::new().id("abc").per_page(80).try_build().is_ok());
+    }
 }
@@ -0,0 +1,341 @@
+//! Colors such as the `avg_color` of photos and videos
+//!
+//! [`RgbColor`] parses the `#RRGGBB` strings of the API and measures how different two
+//! colors look with CIEDE2000, e.g. to sort search results by closeness to a brand color.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, Hex, PexelsError};
+
+/// An sRGB color with 8 bits per channel
+///
+/// # Example
+///
+/// ```
+/// use pexels_api::RgbColor;
+///
+/// let brand: RgbColor = "#1E90FF".parse().unwrap();
+/// let mut colors: Vec<RgbColor> =
+///     ["#FF0000", "#2080F0", "#000080"].iter().map(|c| c.parse().unwrap()).collect();
+/// colors.sort_by(|a, b| brand.distance(a).total_cmp(&brand.distance(b)));
+/// assert_eq!(colors[0].to_string(), "#2080F0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// A color as hue in degrees and saturation and lightness between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// A color in the CIE L*a*b* space under the D65 illuminant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl RgbColor {
+    /// Create a color from its channels
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The color as `#RRGGBB`
+    pub fn to_hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    /// Convert to hue, saturation and lightness
+    pub fn to_hsl(&self) -> Hsl {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| f64::from(c) / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        Hsl { h: h * 60.0, s, l }
+    }
+
+    /// Convert to CIE L*a*b*
+    pub fn to_lab(&self) -> Lab {
+        let linear = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = (linear(self.r), linear(self.g), linear(self.b));
+
+        // Relative to the D65 white point
+        let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+        let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+        let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+
+        let f = |t: f64| {
+            const DELTA: f64 = 6.0 / 29.0;
+            if t > DELTA.powi(3) {
+                t.cbrt()
+            } else {
+                t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        Lab { l: 116.0 * fy - 16.0, a: 500.0 * (fx - fy), b: 200.0 * (fy - fz) }
+    }
+
+    /// Perceptual difference to `other` (CIEDE2000), about 1 is barely noticeable
+    pub fn distance(&self, other: &RgbColor) -> f64 {
+        self.to_lab().delta_e(&other.to_lab())
+    }
+}
+
+impl Lab {
+    /// CIEDE2000 color difference to `other`
+    pub fn delta_e(&self, other: &Lab) -> f64 {
+        let pow7 = |v: f64| v.powi(7);
+        let c_bar = (self.a.hypot(self.b) + other.a.hypot(other.b)) / 2.0;
+        let g = 0.5 * (1.0 - (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt());
+
+        let (a1, a2) = ((1.0 + g) * self.a, (1.0 + g) * other.a);
+        let (c1, c2) = (a1.hypot(self.b), a2.hypot(other.b));
+        let hue = |b: f64, a: f64| {
+            if a == 0.0 && b == 0.0 {
+                0.0
+            } else {
+                b.atan2(a).to_degrees().rem_euclid(360.0)
+            }
+        };
+        let (h1, h2) = (hue(self.b, a1), hue(other.b, a2));
+
+        let delta_l = other.l - self.l;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 > h1 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        let l_bar = (self.l + other.l) / 2.0;
+        let c_bar = (c1 + c2) / 2.0;
+        let h_bar = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let cos = |degrees: f64| degrees.to_radians().cos();
+        let t = 1.0 - 0.17 * cos(h_bar - 30.0)
+            + 0.24 * cos(2.0 * h_bar)
+            + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+        let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+        let r_c = 2.0 * (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt();
+        let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+        let s_c = 1.0 + 0.045 * c_bar;
+        let s_h = 1.0 + 0.015 * c_bar * t;
+        let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+        let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+        (l * l + c * c + h * h + r_t * c * h).sqrt()
+    }
+}
+
+impl FromStr for RgbColor {
+    type Err = PexelsError;
+
+    /// Parse `#RRGGBB`, the `#` is optional and case doesn't matter
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.trim().trim_start_matches('#');
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(PexelsError::HexColorCodeError(format!("{s} is not a #RRGGBB color.")));
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16);
+        match (channel(0), channel(2), channel(4)) {
+            (Ok(r), Ok(g), Ok(b)) => Ok(Self { r, g, b }),
+            _ => Err(PexelsError::HexColorCodeError(format!("{s} is not a #RRGGBB color."))),
+        }
+    }
+}
+
+impl TryFrom<String> for RgbColor {
+    type Error = PexelsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RgbColor> for String {
+    fn from(color: RgbColor) -> Self {
+        color.to_hex()
+    }
+}
+
+impl Display for RgbColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl From<RgbColor> for Hex<'static> {
+    fn from(color: RgbColor) -> Self {
+        Hex::from_rgb(color)
+    }
+}
+
+impl From<RgbColor> for Color<'static> {
+    fn from(color: RgbColor) -> Self {
+        Color::Hex(color.into())
+    }
+}
+
+impl From<&Hex<'_>> for RgbColor {
+    fn from(hex: &Hex<'_>) -> Self {
+        // A `Hex` is always a valid `#RRGGBB` code
+        hex.as_str().parse().unwrap_or(RgbColor::new(0, 0, 0))
+    }
+}
+
+/// Add `avg_rgb` to models with an `avg_color`
+macro_rules! avg_rgb {
+    ($($ty:ty),* $(,)?) => {$(
+        impl $ty {
+            /// The average color, `None` if it is missing or not a `#RRGGBB` code
+            pub fn avg_rgb(&self) -> Option<RgbColor> {
+                self.avg_color.as_deref()?.parse().ok()
+            }
+        }
+    )*};
+}
+
+avg_rgb!(
+    crate::Photo,
+    crate::Video,
+    crate::domain::models::MediaPhoto,
+    crate::domain::models::MediaVideo,
+    crate::models::Photo,
+    crate::models::Video,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let color: RgbColor = "#1e90ff".parse().unwrap();
+        assert_eq!(color, RgbColor::new(0x1E, 0x90, 0xFF));
+        assert_eq!(color.to_string(), "#1E90FF");
+        assert_eq!("ABCDEF".parse::<RgbColor>().unwrap(), RgbColor::new(0xAB, 0xCD, 0xEF));
+        assert!("#12345".parse::<RgbColor>().is_err());
+        assert!("#12345G".parse::<RgbColor>().is_err());
+
+        let color: RgbColor = serde_json::from_str("\"#7A8B9C\"").unwrap();
+        assert_eq!(serde_json::to_string(&color).unwrap(), "\"#7A8B9C\"");
+    }
+
+    #[test]
+    fn test_hsl() {
+        let hsl = RgbColor::new(255, 0, 0).to_hsl();
+        assert_eq!(hsl, Hsl { h: 0.0, s: 1.0, l: 0.5 });
+        let hsl = RgbColor::new(0, 0, 255).to_hsl();
+        assert_close(hsl.h, 240.0);
+        assert_eq!(RgbColor::new(128, 128, 128).to_hsl().s, 0.0);
+    }
+
+    #[test]
+    fn test_lab() {
+        let white = RgbColor::new(255, 255, 255).to_lab();
+        assert!((white.l - 100.0).abs() < 0.01 && white.a.abs() < 0.01 && white.b.abs() < 0.01);
+
+        let red = RgbColor::new(255, 0, 0).to_lab();
+        assert!((red.l - 53.24).abs() < 0.01);
+        assert!((red.a - 80.09).abs() < 0.01);
+        assert!((red.b - 67.20).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_ciede2000() {
+        // Reference pairs from Sharma, Wu and Dalal (2005)
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+        for ((l1, a1, b1), (l2, a2, b2), expected) in pairs {
+            let (x, y) = (Lab { l: l1, a: a1, b: b1 }, Lab { l: l2, a: a2, b: b2 });
+            assert_close(x.delta_e(&y), expected);
+            assert_close(y.delta_e(&x), expected);
+        }
+        assert_eq!(RgbColor::new(1, 2, 3).distance(&RgbColor::new(1, 2, 3)), 0.0);
+    }
+
+    #[test]
+    fn test_into_search_color() {
+        let color = RgbColor::new(0, 0x80, 0xFF);
+        let hex = Hex::from(color);
+        assert_eq!(hex, Hex::from_borrowed_str("#0080FF").unwrap());
+        assert_eq!(RgbColor::from(&hex), color);
+        assert!(matches!(Color::from(color), Color::Hex(_)));
+    }
+
+    #[test]
+    fn test_avg_rgb() {
+        let mut video: crate::Video = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "width": 1920,
+            "height": 1080,
+            "duration": 5,
+            "image": "https://images.pexels.com/videos/1/pictures/preview-0.jpg",
+            "url": "https://www.pexels.com/video/1/",
+            "avg_color": "#5A6B7C",
+            "user": { "id": 2, "name": "Jane", "url": "https://www.pexels.com/@jane" },
+            "video_files": [],
+            "video_pictures": []
+        }))
+        .unwrap();
+        assert_eq!(video.avg_rgb(), Some(RgbColor::new(0x5A, 0x6B, 0x7C)));
+        video.avg_color = Some("blue".to_string());
+        assert_eq!(video.avg_rgb(), None);
+    }
+}
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::schema::{null_as_default, unknown_fields, UnknownFields};
+use crate::{CollectionId, MediaMime, PhotoId, UserId, VideoFileQuality, VideoId};
 
 /// Represents the response for a list of collections.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -9,30 +13,39 @@ pub struct CollectionsResponse {
     pub total_results: u32,
     pub next_page: Option<String>,
     pub prev_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a Pexels collection.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Collection {
-    pub id: String,
+    pub id: CollectionId,
     pub title: String,
     pub description: Option<String>,
     pub private: bool,
     pub media_count: u32,
     pub photos_count: u32,
     pub videos_count: u32,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents the response for a list of media items.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MediaResponse {
-    pub id: String,
+    pub id: CollectionId,
     pub media: Vec<MediaType>, // An array of media objects. Each object has an extra type attribute to indicate the type of object.
     pub page: u32,
     pub per_page: u32,
     pub total_results: u32,
     pub next_page: Option<String>,
     pub prev_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Enum representing the type of media.
@@ -68,17 +81,21 @@ impl From<MediaType> for MediaVideo {
 pub struct MediaPhoto {
     #[serde(skip)]
     pub type_: String,
-    pub id: u32,
+    pub id: PhotoId,
     pub width: u32,
     pub height: u32,
     pub url: Option<String>,
     pub photographer: Option<String>,
     pub photographer_url: Option<String>,
-    pub photographer_id: u32,
-    pub avg_color: String,
+    pub photographer_id: UserId,
+    pub avg_color: Option<String>,
     pub src: PhotoSrc,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub liked: bool,
-    pub alt: String,
+    pub alt: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a video media object.
@@ -86,11 +103,12 @@ pub struct MediaPhoto {
 pub struct MediaVideo {
     #[serde(skip)]
     pub type_: String,
-    pub id: u32,
+    pub id: VideoId,
     pub width: u32,
     pub height: u32,
     pub duration: u32,
     pub full_res: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub tags: Vec<String>,
     pub url: Option<String>,
     pub image: Option<String>,
@@ -98,22 +116,29 @@ pub struct MediaVideo {
     pub user: User,
     pub video_files: Vec<VideoFile>,
     pub video_pictures: Vec<VideoPicture>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a Pexels photo.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Photo {
-    pub id: u32,
+    pub id: PhotoId,
     pub width: u32,
     pub height: u32,
     pub url: String,
     pub photographer: String,
-    pub photographer_url: String,
-    pub photographer_id: u32,
-    pub avg_color: String,
+    pub photographer_url: Option<String>,
+    pub photographer_id: UserId,
+    pub avg_color: Option<String>,
     pub src: PhotoSrc,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub liked: bool,
-    pub alt: String,
+    pub alt: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents different image sizes for a photo.
@@ -127,6 +152,9 @@ pub struct PhotoSrc {
     pub portrait: String,
     pub landscape: String,
     pub tiny: String,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents the response for a list of photos.
@@ -138,6 +166,9 @@ pub struct PhotosResponse {
     pub photos: Vec<Photo>,
     pub next_page: Option<String>,
     pub prev_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents the response for a list of videos.
@@ -146,10 +177,14 @@ pub struct VideoResponse {
     pub page: u32,
     pub per_page: u32,
     pub total_results: u32,
-    pub url: String,
+    #[serde(default)]
+    pub url: Option<String>,
     pub videos: Vec<Video>,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a Pexels video.
@@ -161,9 +196,10 @@ pub struct Video {
     #[serde(default)]
     pub full_res: Option<String>,
     pub height: u32,
-    pub id: u32,
+    pub id: VideoId,
     #[serde(rename = "image")]
     pub image_url: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub tags: Vec<String>,
     #[serde(rename = "url")]
     pub video_url: String,
@@ -171,37 +207,73 @@ pub struct Video {
     pub video_files: Vec<VideoFile>,
     pub video_pictures: Vec<VideoPicture>,
     pub width: u32,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a user who created a media item.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct User {
-    pub id: u32,
+    pub id: UserId,
     pub name: String,
     #[serde(rename = "url")]
     pub user_url: String,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a video file with different qualities.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VideoFile {
-    pub file_type: String,
-    pub fps: f64,
-    pub height: u32,
-    pub id: u32,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub file_type: MediaMime,
+    pub fps: Option<f64>,
+    pub height: Option<u32>,
+    pub id: u64,
     #[serde(rename = "link")]
     pub file_link: String,
     #[serde(default)]
-    pub quality: Option<String>,
-    pub size: u64,
-    pub width: u32,
+    pub quality: Option<VideoFileQuality>,
+    pub size: Option<u64>,
+    pub width: Option<u32>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Represents a preview picture of a video.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VideoPicture {
-    pub id: u32,
+    pub id: u64,
     pub nr: u32,
     #[serde(rename = "picture")]
     pub picture_url: String,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+unknown_fields!(CollectionsResponse, collections);
+unknown_fields!(Collection);
+unknown_fields!(MediaResponse, media);
+unknown_fields!(MediaPhoto, src);
+unknown_fields!(MediaVideo, user, video_files, video_pictures);
+unknown_fields!(Photo, src);
+unknown_fields!(PhotoSrc);
+unknown_fields!(PhotosResponse, photos);
+unknown_fields!(VideoResponse, videos);
+unknown_fields!(Video, user, video_files, video_pictures);
+unknown_fields!(User);
+unknown_fields!(VideoFile);
+unknown_fields!(VideoPicture);
+
+impl UnknownFields for MediaType {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        match self {
+            MediaType::Photo(photo) => photo.collect_unknown_fields(path, fields),
+            MediaType::Video(video) => video.collect_unknown_fields(path, fields),
+        }
+    }
 }
@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::models::{Photo, Video, VideoFile};
+use crate::{PexelsError, UserId};
+
+use super::format::MediaKind;
+
+/// File format of an attribution sidecar written next to a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SidecarFormat {
+    /// `<file name>.json`, e.g. `photo_1.jpg.json`
+    Json,
+    /// `<file stem>.xmp`, e.g. `photo_1.xmp`, as expected by Lightroom and most DAMs
+    Xmp,
+}
+
+impl SidecarFormat {
+    /// Path of the sidecar for the media file at `path`
+    pub fn path(&self, path: &Path) -> PathBuf {
+        match self {
+            SidecarFormat::Json => {
+                let mut name = path.file_name().unwrap_or_default().to_os_string();
+                name.push(".json");
+                path.with_file_name(name)
+            }
+            SidecarFormat::Xmp => path.with_extension("xmp"),
+        }
+    }
+}
+
+/// Credit and provenance of a downloaded photo or video
+///
+/// Pexels asks to credit the photographer, this carries everything needed to do so.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attribution {
+    pub kind: MediaKind,
+    /// Pexels id of the photo or video
+    pub id: u64,
+    /// Page of the media on pexels.com
+    pub url: String,
+    pub photographer: String,
+    pub photographer_url: Option<String>,
+    pub photographer_id: Option<UserId>,
+    pub alt: Option<String>,
+    pub avg_color: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    /// URL of the downloaded rendition
+    pub source_url: String,
+    /// Quality label of the downloaded rendition, e.g. `large2x`
+    pub quality: String,
+    /// Download time in RFC 3339, set when the sidecar is written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downloaded_at: Option<String>,
+}
+
+impl Attribution {
+    /// Attribution of a photo downloaded from `source_url`
+    pub fn from_photo(photo: &Photo, source_url: &str, quality: &str) -> Self {
+        Self {
+            kind: MediaKind::Image,
+            id: photo.id.get(),
+            url: photo.url.clone(),
+            photographer: photo.photographer.clone(),
+            photographer_url: photo.photographer_url.clone(),
+            photographer_id: photo.photographer_id,
+            alt: photo.alt.clone().filter(|alt| !alt.is_empty()),
+            avg_color: photo.avg_color.clone(),
+            width: photo.width,
+            height: photo.height,
+            source_url: source_url.to_string(),
+            quality: quality.to_string(),
+            downloaded_at: None,
+        }
+    }
+
+    /// Attribution of a video file (rendition)
+    pub fn from_video(video: &Video, file: &VideoFile, quality: &str) -> Self {
+        Self {
+            kind: MediaKind::Video,
+            id: video.id.get(),
+            url: video.url.clone(),
+            photographer: video.user.name.clone(),
+            photographer_url: Some(video.user.user_url.clone()).filter(|url| !url.is_empty()),
+            photographer_id: Some(video.user.id),
+            alt: None,
+            avg_color: None,
+            width: file.width.unwrap_or(video.width),
+            height: file.height.unwrap_or(video.height),
+            source_url: file.link.clone(),
+            quality: quality.to_string(),
+            downloaded_at: None,
+        }
+    }
+
+    /// Credit line as suggested by Pexels, e.g. `Photo by Jane Doe on Pexels`
+    pub fn credit(&self) -> String {
+        let kind = match self.kind {
+            MediaKind::Image => "Photo",
+            MediaKind::Video => "Video",
+        };
+        format!("{kind} by {} on Pexels", self.photographer)
+    }
+
+    /// XMP packet with Dublin Core, Photoshop and IPTC Core properties
+    pub fn to_xmp(&self) -> String {
+        let mut attributes = vec![
+            ("photoshop:Credit", self.credit()),
+            ("photoshop:Source", self.url.clone()),
+            ("xmpRights:WebStatement", self.url.clone()),
+            ("dc:source", self.url.clone()),
+        ];
+        if let Some(date) = &self.downloaded_at {
+            attributes.push(("xmp:MetadataDate", date.clone()));
+        }
+
+        let mut xmp = String::from(concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "  <rdf:Description rdf:about=\"\"\n",
+            "    xmlns:dc=\"http://purl.org/dc/elements/1.1/\"\n",
+            "    xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"\n",
+            "    xmlns:xmpRights=\"http://ns.adobe.com/xap/1.0/rights/\"\n",
+            "    xmlns:photoshop=\"http://ns.adobe.com/photoshop/1.0/\"\n",
+            "    xmlns:Iptc4xmpCore=\"http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/\"",
+        ));
+        for (name, value) in attributes {
+            xmp.push_str(&format!("\n    {name}=\"{}\"", xml_escape(&value)));
+        }
+        xmp.push_str(">\n");
+
+        xmp.push_str(&format!(
+            "   <dc:creator><rdf:Seq><rdf:li>{}</rdf:li></rdf:Seq></dc:creator>\n",
+            xml_escape(&self.photographer)
+        ));
+        if let Some(alt) = &self.alt {
+            xmp.push_str(&format!(
+                "   <dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:description>\n",
+                xml_escape(alt)
+            ));
+        }
+        if let Some(url) = &self.photographer_url {
+            xmp.push_str(&format!(
+                "   <Iptc4xmpCore:CreatorContactInfo rdf:parseType=\"Resource\"><Iptc4xmpCore:CiUrlWork>{}</Iptc4xmpCore:CiUrlWork></Iptc4xmpCore:CreatorContactInfo>\n",
+                xml_escape(url)
+            ));
+        }
+
+        xmp.push_str("  </rdf:Description>\n </rdf:RDF>\n</x:xmpmeta>\n<?xpacket end=\"w\"?>");
+        xmp
+    }
+}
+
+/// Write the sidecars of the media file at `path`
+///
+/// With `overwrite` unset, existing sidecars are left alone.
+pub(crate) async fn write_sidecars(
+    path: &Path,
+    attribution: &Attribution,
+    formats: &[SidecarFormat],
+    overwrite: bool,
+) -> Result<(), PexelsError> {
+    let mut attribution = attribution.clone();
+    attribution.downloaded_at = Some(now_rfc3339());
+
+    for format in formats {
+        let sidecar = format.path(path);
+        if !overwrite && sidecar.exists() {
+            continue;
+        }
+        let contents = match format {
+            SidecarFormat::Json => serde_json::to_vec_pretty(&attribution)?,
+            SidecarFormat::Xmp => attribution.to_xmp().into_bytes(),
+        };
+        fs::write(&sidecar, contents).await?;
+    }
+    Ok(())
+}
+
+/// Remove every kind of sidecar of the media file at `path`
+pub(crate) async fn remove_sidecars(path: &Path) -> std::io::Result<()> {
+    for format in [SidecarFormat::Json, SidecarFormat::Xmp] {
+        match fs::remove_file(format.path(path)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\n' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn now_rfc3339() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    rfc3339(secs)
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 UTC timestamp
+pub(crate) fn rfc3339(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribution() -> Attribution {
+        Attribution {
+            kind: MediaKind::Image,
+            id: 1,
+            url: "https://www.pexels.com/photo/1/".to_string(),
+            photographer: "Jane & Doe".to_string(),
+            photographer_url: Some("https://www.pexels.com/@jane".to_string()),
+            photographer_id: Some(UserId::new(7)),
+            alt: Some("A \"quiet\" lake".to_string()),
+            avg_color: Some("#7E8C91".to_string()),
+            width: 800,
+            height: 600,
+            source_url: "https://images.pexels.com/photos/1/photo.jpeg".to_string(),
+            quality: "original".to_string(),
+            downloaded_at: None,
+        }
+    }
+
+    #[test]
+    fn test_sidecar_paths() {
+        let path = Path::new("out/photo_1.jpg");
+        assert_eq!(SidecarFormat::Json.path(path), PathBuf::from("out/photo_1.jpg.json"));
+        assert_eq!(SidecarFormat::Xmp.path(path), PathBuf::from("out/photo_1.xmp"));
+    }
+
+    #[test]
+    fn test_xmp_is_escaped() {
+        let xmp = attribution().to_xmp();
+        assert!(xmp.contains("<rdf:li>Jane &amp; Doe</rdf:li>"));
+        assert!(xmp.contains("A &quot;quiet&quot; lake"));
+        assert!(xmp.contains("photoshop:Credit=\"Photo by Jane &amp; Doe on Pexels\""));
+        assert!(xmp.ends_with("<?xpacket end=\"w\"?>"));
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+    }
+}
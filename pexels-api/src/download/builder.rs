@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Client;
+
+use crate::PexelsError;
+
+use super::host_limit::HostLimits;
+use super::DownloadManager;
+
+/// Default maximum number of concurrent downloads
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+/// Default time allowed to establish a connection
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time a transfer may stall without receiving any data
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default number of idle connections kept per host
+const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 20;
+
+/// Default time an idle connection is kept, the same as reqwest's
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Builder for [`DownloadManager`] with control over timeouts, the connection pool, per-host
+/// concurrency and HTTP/2
+///
+/// By default there is no total timeout, so large videos on slow links are only cut off when
+/// they stall for longer than the read timeout.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use pexels_api::DownloadManager;
+///
+/// let manager = DownloadManager::builder()
+///     .max_concurrent(8)
+///     .connect_timeout(Duration::from_secs(10))
+///     .read_timeout(Duration::from_secs(30))
+///     .host_limit("videos.pexels.com", 2)
+///     .host_limit("images.pexels.com", 8)
+///     .http2_adaptive_window(true)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DownloadManagerBuilder {
+    max_concurrent: usize,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    pool_max_idle_per_host: usize,
+    pool_idle_timeout: Option<Duration>,
+    host_limits: HashMap<String, usize>,
+    default_host_limit: Option<usize>,
+    http2_prior_knowledge: bool,
+    http2_adaptive_window: bool,
+    http2_initial_stream_window_size: Option<u32>,
+    http2_keep_alive_interval: Option<Duration>,
+}
+
+impl Default for DownloadManagerBuilder {
+    fn default() -> Self {
+        Self {
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            read_timeout: Some(DEFAULT_READ_TIMEOUT),
+            timeout: None,
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            host_limits: HashMap::new(),
+            default_host_limit: None,
+            http2_prior_knowledge: false,
+            http2_adaptive_window: false,
+            http2_initial_stream_window_size: None,
+            http2_keep_alive_interval: None,
+        }
+    }
+}
+
+impl DownloadManagerBuilder {
+    /// Creates a new [`DownloadManagerBuilder`] with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of concurrent downloads of a batch
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent;
+        self
+    }
+
+    /// Sets the time allowed to establish a connection, 30 seconds by default
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long a transfer may go without receiving data, 60 seconds by default
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a limit for each request from start to the end of its body, none by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of idle connections kept per host, 20 by default
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self
+    }
+
+    /// Sets how long idle connections are kept, 90 seconds by default
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the number of concurrent transfers from `host`, e.g. `videos.pexels.com`
+    pub fn host_limit(mut self, host: impl Into<String>, max_concurrent: usize) -> Self {
+        self.host_limits.insert(host.into().to_ascii_lowercase(), max_concurrent);
+        self
+    }
+
+    /// Limits the number of concurrent transfers from each host without its own limit
+    pub fn default_host_limit(mut self, max_concurrent: usize) -> Self {
+        self.default_host_limit = Some(max_concurrent);
+        self
+    }
+
+    /// Only speak HTTP/2, without negotiating it first
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Adapt the HTTP/2 flow control windows to the measured bandwidth
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Sets the initial HTTP/2 stream window size in bytes
+    pub fn http2_initial_stream_window_size(mut self, size: u32) -> Self {
+        self.http2_initial_stream_window_size = Some(size);
+        self
+    }
+
+    /// Sends HTTP/2 pings at this interval to keep multiplexed connections alive
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Create the [`DownloadManager`]
+    ///
+    /// # Errors
+    /// Fails if the HTTP client cannot be created, e.g. when no TLS backend is available
+    pub fn build(self) -> Result<DownloadManager, PexelsError> {
+        let client = self.client()?;
+        let mut manager = DownloadManager::with_client(client, self.max_concurrent);
+        manager.host_limits = Arc::new(HostLimits::new(self.host_limits, self.default_host_limit));
+        Ok(manager)
+    }
+
+    /// The HTTP client with the configured timeouts, pool and HTTP/2 settings
+    pub(crate) fn client(&self) -> reqwest::Result<Client> {
+        let mut builder = Client::builder()
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .http2_adaptive_window(self.http2_adaptive_window)
+            .http2_initial_stream_window_size(self.http2_initial_stream_window_size)
+            .http2_keep_alive_interval(self.http2_keep_alive_interval);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let builder = DownloadManagerBuilder::new();
+        assert_eq!(builder.max_concurrent, DEFAULT_MAX_CONCURRENT);
+        assert_eq!(builder.connect_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(builder.read_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(builder.timeout, None);
+        assert_eq!(builder.pool_max_idle_per_host, 20);
+        assert_eq!(builder.pool_idle_timeout, Some(Duration::from_secs(90)));
+        assert!(builder.host_limits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build() {
+        let manager = DownloadManagerBuilder::new()
+            .max_concurrent(2)
+            .timeout(Duration::from_secs(600))
+            .host_limit("Videos.Pexels.com", 1)
+            .http2_keep_alive_interval(Duration::from_secs(20))
+            .build()
+            .unwrap();
+        assert_eq!(manager.max_concurrent, 2);
+
+        let video = manager.host_limits.acquire("https://videos.pexels.com/a.mp4").await;
+        assert!(video.is_some());
+        let second = manager.host_limits.acquire("https://videos.pexels.com/b.mp4");
+        assert!(tokio::time::timeout(Duration::from_millis(50), second).await.is_err());
+        assert!(manager.host_limits.acquire("https://images.pexels.com/a.jpg").await.is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+
+/// Hash algorithm used to fingerprint downloaded files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Sha256,
+    /// Requires the `blake3` feature
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            #[cfg(feature = "blake3")]
+            ChecksumAlgorithm::Blake3 => "blake3",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Digest of a file, displayed as `algorithm:hex`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checksum {
+    pub algorithm: ChecksumAlgorithm,
+    /// Lowercase hexadecimal digest
+    pub hex: String,
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
+
+/// Incremental hasher fed while a download streams
+pub(crate) enum Hasher {
+    Sha256(Sha256),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    pub(crate) fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            #[cfg(feature = "blake3")]
+            ChecksumAlgorithm::Blake3 => Hasher::Blake3(Box::default()),
+        }
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    pub(crate) fn finalize(self) -> Checksum {
+        match self {
+            Hasher::Sha256(hasher) => {
+                Checksum { algorithm: ChecksumAlgorithm::Sha256, hex: to_hex(&hasher.finalize()) }
+            }
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => Checksum {
+                algorithm: ChecksumAlgorithm::Blake3,
+                hex: hasher.finalize().to_hex().to_string(),
+            },
+        }
+    }
+
+    /// Feed the contents of a file, e.g. the part already on disk before resuming
+    pub(crate) async fn update_from_file(&mut self, path: &Path) -> std::io::Result<u64> {
+        let mut file = fs::File::open(path).await?;
+        let mut buffer = vec![0; 64 * 1024];
+        let mut total = 0;
+        loop {
+            let n = file.read(&mut buffer).await?;
+            if n == 0 {
+                return Ok(total);
+            }
+            self.update(&buffer[..n]);
+            total += n as u64;
+        }
+    }
+}
+
+/// Size and checksum of a file on disk
+pub(crate) async fn checksum_file(
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+) -> std::io::Result<(u64, Checksum)> {
+    let mut hasher = Hasher::new(algorithm);
+    let size = hasher.update_from_file(path).await?;
+    Ok((size, hasher.finalize()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256() {
+        let mut hasher = Hasher::new(ChecksumAlgorithm::Sha256);
+        hasher.update(b"ab");
+        hasher.update(b"c");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_blake3() {
+        let mut hasher = Hasher::new(ChecksumAlgorithm::Blake3);
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.finalize().hex,
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::path::Path;
+
+use crate::models::MediaItem;
+use crate::search::CollectionMediaParams;
+use crate::{CollectionId, MediaType, PexelsClient, PexelsError};
+
+use super::format::MediaKind;
+use super::naming::{sanitize_segment, UniqueNames};
+use super::{
+    BatchReport, DownloadManager, ImageQuality, ProgressCallback, RenditionSelector, VideoQuality,
+};
+
+/// Largest page size the Pexels API accepts
+const MAX_PER_PAGE: u32 = 80;
+
+/// What to download from a collection and how
+///
+/// By default every photo in original quality and every video in HD is downloaded into a
+/// folder named after the collection id.
+#[derive(Debug, Clone)]
+pub struct CollectionDownloadOptions {
+    media_type: Option<MediaType>,
+    photo_quality: ImageQuality,
+    video_selector: RenditionSelector,
+    video_quality: Option<String>,
+    max_items: Option<usize>,
+    folder: Option<String>,
+}
+
+impl Default for CollectionDownloadOptions {
+    fn default() -> Self {
+        Self {
+            media_type: None,
+            photo_quality: ImageQuality::Original,
+            video_selector: VideoQuality::HD.into(),
+            video_quality: Some(VideoQuality::HD.to_string()),
+            max_items: None,
+            folder: None,
+        }
+    }
+}
+
+impl CollectionDownloadOptions {
+    /// Creates new [`CollectionDownloadOptions`] with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only download photos or only videos
+    pub fn media_type(mut self, media_type: MediaType) -> Self {
+        self.media_type = Some(media_type).filter(|media_type| media_type != &MediaType::Empty);
+        self
+    }
+
+    /// Quality of the photos
+    pub fn photo_quality(mut self, quality: ImageQuality) -> Self {
+        self.photo_quality = quality;
+        self
+    }
+
+    /// Quality of the videos
+    pub fn video_quality(mut self, quality: VideoQuality) -> Self {
+        self.video_selector = quality.into();
+        self.video_quality = Some(quality.to_string());
+        self
+    }
+
+    /// Choose each video's rendition with a [`RenditionSelector`]
+    pub fn video_selector(mut self, selector: RenditionSelector) -> Self {
+        self.video_selector = selector;
+        self.video_quality = None;
+        self
+    }
+
+    /// Stop after this many media items
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Name of the folder below the output directory, the collection id by default
+    pub fn folder(mut self, folder: impl Into<String>) -> Self {
+        self.folder = Some(folder.into());
+        self
+    }
+}
+
+impl DownloadManager {
+    /// Download every photo and video of a collection
+    ///
+    /// Pages through `/collections/:id` and downloads the media into a per-collection folder
+    /// below `output_dir`. The collection id is available to the file name templates as
+    /// `{collection}`.
+    ///
+    /// # Arguments
+    /// * `client` - Client used to list the collection
+    /// * `id` - Collection id
+    /// * `output_dir` - Output directory
+    /// * `options` - Media type, qualities, limit and folder
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A report of the downloaded files and the media that failed
+    ///
+    /// # Errors
+    /// Fails if the collection cannot be listed or the output directory cannot be pruned.
+    /// Failed downloads are reported in the [`BatchReport`] instead.
+    pub async fn download_collection<P: AsRef<Path>>(
+        &self,
+        client: &PexelsClient,
+        id: impl Into<CollectionId>,
+        output_dir: P,
+        options: &CollectionDownloadOptions,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<BatchReport, PexelsError> {
+        let id = id.into();
+        let media = list_collection(client, &id, options).await?;
+
+        let id = id.as_str();
+        let folder = sanitize_segment(options.folder.as_deref().unwrap_or(id));
+        let output_dir = output_dir.as_ref().join(folder);
+        let mut names = UniqueNames::default();
+
+        let items = media
+            .iter()
+            .map(|item| match item {
+                MediaItem::Photo(photo) => {
+                    let url = self.get_photo_url(photo, options.photo_quality);
+                    let quality = options.photo_quality.to_string();
+                    let mut target = self.photo_target(photo, &quality, url, Some(id));
+                    target.file_name = names.claim(target.file_name);
+                    (MediaKind::Image, photo.id.get(), Ok(target))
+                }
+                MediaItem::Video(video) => {
+                    let target = self
+                        .video_target(
+                            video,
+                            &options.video_selector,
+                            options.video_quality.clone(),
+                            Some(id),
+                        )
+                        .map(|mut target| {
+                            target.file_name = names.claim(target.file_name);
+                            target
+                        });
+                    (MediaKind::Video, video.id.get(), target)
+                }
+            })
+            .collect();
+
+        let report = self.run_batch(items, &output_dir, progress_callback).await?;
+
+        for kind in [MediaKind::Image, MediaKind::Video] {
+            let ids = media
+                .iter()
+                .filter_map(|item| match item {
+                    MediaItem::Photo(photo) if kind == MediaKind::Image => Some(photo.id.get()),
+                    MediaItem::Video(video) if kind == MediaKind::Video => Some(video.id.get()),
+                    _ => None,
+                })
+                .collect();
+            self.prune_batch(&output_dir, kind, &ids).await?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Fetch every page of the collection, up to `max_items`
+async fn list_collection(
+    client: &PexelsClient,
+    id: &CollectionId,
+    options: &CollectionDownloadOptions,
+) -> Result<Vec<MediaItem>, PexelsError> {
+    let max_items = options.max_items.unwrap_or(usize::MAX);
+    let mut media = Vec::new();
+
+    for page in 1.. {
+        let mut params = CollectionMediaParams::new().page(page).per_page(MAX_PER_PAGE);
+        if let Some(media_type) = &options.media_type {
+            params = params.media_type(media_type.clone());
+        }
+
+        let response = client.get_collection_media_with_params(id, &params).await?;
+        let done = response.next_page.is_none() || response.media.is_empty();
+        media.extend(response.media);
+
+        if done || media.len() >= max_items {
+            break;
+        }
+    }
+
+    media.truncate(max_items);
+    Ok(media)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        json_response, manager, response, serve, serve_base_url, temp_dir, PNG,
+    };
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_download_collection() {
+        let photo_url = serve(vec![response("200 OK", PNG)]).await;
+        let src = json!({
+            "original": photo_url, "large2x": photo_url, "large": photo_url,
+            "medium": photo_url, "small": photo_url, "portrait": photo_url,
+            "landscape": photo_url, "tiny": photo_url,
+        });
+        let photo = |id: u64| {
+            json!({
+                "type": "Photo", "id": id, "width": 1, "height": 1,
+                "url": format!("https://www.pexels.com/photo/{id}/"), "photographer": "Jane",
+                "photographer_url": null, "photographer_id": null, "avg_color": null,
+                "src": src, "alt": null,
+            })
+        };
+        let page = json!({
+            "id": "abc", "media": [photo(1), photo(2)], "page": 1, "per_page": 80,
+            "total_results": 2, "next_page": null, "prev_page": null,
+        });
+        let api_url = serve_base_url(vec![json_response(&page)]).await;
+        let client = PexelsClient::new("key").with_base_url(api_url);
+
+        let dir = temp_dir("collection");
+        let options = CollectionDownloadOptions::new().max_items(1).folder("My/Favourites");
+        let report =
+            manager().download_collection(&client, "abc", &dir, &options, None).await.unwrap();
+
+        assert!(report.is_complete());
+        assert_eq!(report.downloaded.len(), 1);
+        assert!(report.downloaded[0].path.starts_with(dir.join("My_Favourites")));
+        assert_eq!(std::fs::read(&report.downloaded[0].path).unwrap(), PNG);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
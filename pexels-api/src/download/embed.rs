@@ -0,0 +1,314 @@
+use std::path::Path;
+
+use tokio::fs;
+
+use crate::PexelsError;
+
+use super::attribution::Attribution;
+use super::format::MediaFormat;
+
+/// Signature of an XMP packet in a JPEG APP1 segment
+const XMP_NAMESPACE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Signature of Photoshop image resources in a JPEG APP13 segment
+const PHOTOSHOP_NAMESPACE: &[u8] = b"Photoshop 3.0\0";
+
+/// Keyword of the PNG `iTXt` chunk holding XMP
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Write the attribution into the metadata of a downloaded JPEG or PNG file
+///
+/// JPEGs get an XMP packet (`dc:creator`, `dc:description`, `photoshop:Credit`,
+/// `photoshop:Source`) and IPTC IIM by-line, caption, credit and source. PNGs only support
+/// XMP, stored in an `iTXt` chunk. Previous XMP and IPTC metadata is replaced, everything
+/// else is kept. The file is replaced atomically.
+///
+/// # Errors
+/// Returns [`PexelsError::MetadataError`] for other formats or malformed files.
+pub async fn embed_attribution<P: AsRef<Path>>(
+    path: P,
+    attribution: &Attribution,
+) -> Result<(), PexelsError> {
+    let path = path.as_ref();
+    let data = fs::read(path).await?;
+    let embedded = embed_attribution_bytes(&data, attribution)?;
+
+    let mut temp = path.as_os_str().to_os_string();
+    temp.push(".tmp");
+    let written = match fs::write(&temp, embedded).await {
+        Ok(()) => fs::rename(&temp, path).await,
+        Err(error) => Err(error),
+    };
+    if written.is_err() {
+        // Don't leave a half written copy next to the intact original
+        let _ = fs::remove_file(&temp).await;
+    }
+    Ok(written?)
+}
+
+/// Write the attribution into the metadata of JPEG or PNG data in memory
+///
+/// See [`embed_attribution`].
+pub fn embed_attribution_bytes(
+    data: &[u8],
+    attribution: &Attribution,
+) -> Result<Vec<u8>, PexelsError> {
+    let xmp = attribution.to_xmp();
+    match MediaFormat::from_magic_bytes(data) {
+        Some(MediaFormat::Jpeg) => embed_jpeg(data, &xmp, &iptc(attribution)),
+        Some(MediaFormat::Png) => embed_png(data, &xmp),
+        format => Err(PexelsError::MetadataError(format!(
+            "embedding requires JPEG or PNG, found {}",
+            format.map_or_else(|| "unknown data".to_string(), |format| format.to_string())
+        ))),
+    }
+}
+
+fn embed_jpeg(data: &[u8], xmp: &str, iptc: &[u8]) -> Result<Vec<u8>, PexelsError> {
+    let malformed = || PexelsError::MetadataError("malformed JPEG".to_string());
+
+    // Segments up to the start of scan, the compressed data after it is copied as is
+    let mut segments = Vec::new();
+    let mut pos = 2;
+    let rest = loop {
+        if data.get(pos) != Some(&0xFF) {
+            return Err(malformed());
+        }
+        while data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        let marker = *data.get(pos + 1).ok_or_else(malformed)?;
+        if marker == 0xDA {
+            break &data[pos..];
+        }
+        let len = data.get(pos + 2..pos + 4).ok_or_else(malformed)?;
+        let len = usize::from(u16::from_be_bytes([len[0], len[1]]));
+        if len < 2 {
+            return Err(malformed());
+        }
+        let end = pos + 2 + len;
+        let segment = data.get(pos..end).ok_or_else(malformed)?;
+        segments.push((marker, segment));
+        pos = end;
+    };
+
+    let xmp_segment = jpeg_segment(0xE1, &[XMP_NAMESPACE, xmp.as_bytes()])?;
+    let iptc_segment = jpeg_segment(0xED, &[PHOTOSHOP_NAMESPACE, &photoshop_irb(iptc)])?;
+
+    let mut out = Vec::with_capacity(data.len() + xmp_segment.len() + iptc_segment.len());
+    out.extend_from_slice(&data[..2]);
+
+    // JFIF and Exif must stay first, the new metadata follows them
+    let leading = segments
+        .iter()
+        .take_while(|(marker, segment)| {
+            *marker == 0xE0 || (*marker == 0xE1 && segment[4..].starts_with(b"Exif\0"))
+        })
+        .count();
+    for (_, segment) in &segments[..leading] {
+        out.extend_from_slice(segment);
+    }
+    out.extend_from_slice(&xmp_segment);
+    out.extend_from_slice(&iptc_segment);
+    for (marker, segment) in &segments[leading..] {
+        let payload = &segment[4..];
+        let replaced = (*marker == 0xE1 && payload.starts_with(XMP_NAMESPACE))
+            || (*marker == 0xED && payload.starts_with(PHOTOSHOP_NAMESPACE));
+        if !replaced {
+            out.extend_from_slice(segment);
+        }
+    }
+    out.extend_from_slice(rest);
+    Ok(out)
+}
+
+fn jpeg_segment(marker: u8, parts: &[&[u8]]) -> Result<Vec<u8>, PexelsError> {
+    let len = 2 + parts.iter().map(|part| part.len()).sum::<usize>();
+    let len = u16::try_from(len)
+        .map_err(|_| PexelsError::MetadataError("metadata exceeds a JPEG segment".to_string()))?;
+
+    let mut segment = vec![0xFF, marker];
+    segment.extend_from_slice(&len.to_be_bytes());
+    for part in parts {
+        segment.extend_from_slice(part);
+    }
+    Ok(segment)
+}
+
+/// IPTC IIM records for by-line, caption, credit and source, encoded as UTF-8
+fn iptc(attribution: &Attribution) -> Vec<u8> {
+    let mut records = Vec::new();
+    let mut dataset = |record: u8, tag: u8, value: &[u8]| {
+        // Non-extended datasets are limited to 32767 bytes
+        let value = &value[..value.len().min(0x7FFF)];
+        records.extend_from_slice(&[0x1C, record, tag]);
+        records.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        records.extend_from_slice(value);
+    };
+
+    // Coded character set: UTF-8
+    dataset(1, 90, b"\x1b%G");
+    dataset(2, 80, attribution.photographer.as_bytes());
+    if let Some(alt) = &attribution.alt {
+        dataset(2, 120, alt.as_bytes());
+    }
+    dataset(2, 110, attribution.credit().as_bytes());
+    dataset(2, 115, attribution.url.as_bytes());
+    records
+}
+
+/// Photoshop image resource block 0x0404 (IPTC-NAA) holding the IIM records
+fn photoshop_irb(iptc: &[u8]) -> Vec<u8> {
+    let mut irb = Vec::with_capacity(iptc.len() + 13);
+    irb.extend_from_slice(b"8BIM");
+    irb.extend_from_slice(&0x0404u16.to_be_bytes());
+    // Empty Pascal string name, padded to an even length
+    irb.extend_from_slice(&[0, 0]);
+    irb.extend_from_slice(&(iptc.len() as u32).to_be_bytes());
+    irb.extend_from_slice(iptc);
+    if iptc.len() % 2 == 1 {
+        irb.push(0);
+    }
+    irb
+}
+
+fn embed_png(data: &[u8], xmp: &str) -> Result<Vec<u8>, PexelsError> {
+    let malformed = || PexelsError::MetadataError("malformed PNG".to_string());
+
+    let mut itxt = Vec::with_capacity(xmp.len() + PNG_XMP_KEYWORD.len() + 5);
+    itxt.extend_from_slice(PNG_XMP_KEYWORD);
+    // Null separator, no compression, empty language tag and translated keyword
+    itxt.extend_from_slice(&[0, 0, 0, 0, 0]);
+    itxt.extend_from_slice(xmp.as_bytes());
+
+    let mut out = Vec::with_capacity(data.len() + itxt.len() + 12);
+    out.extend_from_slice(PNG_SIGNATURE);
+
+    let mut pos = PNG_SIGNATURE.len();
+    while pos < data.len() {
+        let header = data.get(pos..pos + 8).ok_or_else(malformed)?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let chunk_type = &header[4..8];
+        let end = pos + 12 + len;
+        let chunk = data.get(pos..end).ok_or_else(malformed)?;
+
+        let is_xmp = chunk_type == b"iTXt"
+            && chunk[8..].starts_with(PNG_XMP_KEYWORD)
+            && chunk.get(8 + PNG_XMP_KEYWORD.len()) == Some(&0);
+        if !is_xmp {
+            out.extend_from_slice(chunk);
+        }
+        // The XMP goes right after the header so readers find it early
+        if chunk_type == b"IHDR" {
+            out.extend_from_slice(&png_chunk(b"iTXt", &itxt));
+        }
+        pos = end;
+    }
+    Ok(out)
+}
+
+fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(data.len() + 12);
+    chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(chunk_type);
+    chunk.extend_from_slice(data);
+    chunk.extend_from_slice(&crc32(&chunk[4..]).to_be_bytes());
+    chunk
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes
+        .iter()
+        .fold(!0u32, |crc, &b| CRC_TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::download::format::MediaKind;
+
+    fn attribution() -> Attribution {
+        Attribution {
+            kind: MediaKind::Image,
+            id: 1,
+            url: "https://www.pexels.com/photo/1/".to_string(),
+            photographer: "Jane Doe".to_string(),
+            photographer_url: None,
+            photographer_id: None,
+            alt: Some("Lake".to_string()),
+            avg_color: None,
+            width: 1,
+            height: 1,
+            source_url: String::new(),
+            quality: "original".to_string(),
+            downloaded_at: None,
+        }
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).filter(|w| *w == needle).count()
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_jpeg_metadata_is_replaced() {
+        let jfif = [0xFF, 0xE0, 0, 6, b'J', b'F', b'I', b'F'];
+        let scan = [0xFF, 0xDA, 0, 2, 0x12, 0x34, 0xFF, 0xD9];
+        let jpeg = [&[0xFF, 0xD8][..], &jfif, &scan].concat();
+
+        let once = embed_attribution_bytes(&jpeg, &attribution()).unwrap();
+        let twice = embed_attribution_bytes(&once, &attribution()).unwrap();
+        assert_eq!(once, twice);
+
+        assert!(once[2..].starts_with(&jfif));
+        assert!(once.ends_with(&scan));
+        assert_eq!(find(&once, XMP_NAMESPACE), 1);
+        assert_eq!(find(&once, b"<rdf:li>Jane Doe</rdf:li>"), 1);
+        assert_eq!(find(&once, b"\x1c\x02\x50\x00\x08Jane Doe"), 1);
+    }
+
+    #[test]
+    fn test_png_gets_itxt_after_header() {
+        let ihdr = png_chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+        let iend = png_chunk(b"IEND", &[]);
+        let png = [PNG_SIGNATURE, &ihdr, &iend].concat();
+
+        let once = embed_attribution_bytes(&png, &attribution()).unwrap();
+        let twice = embed_attribution_bytes(&once, &attribution()).unwrap();
+        assert_eq!(once, twice);
+
+        let itxt_at = PNG_SIGNATURE.len() + ihdr.len();
+        assert_eq!(&once[itxt_at + 4..itxt_at + 8], b"iTXt");
+        assert!(once.ends_with(&iend));
+    }
+
+    #[test]
+    fn test_rejects_other_formats() {
+        assert!(matches!(
+            embed_attribution_bytes(b"GIF89a", &attribution()),
+            Err(PexelsError::MetadataError(_))
+        ));
+    }
+}
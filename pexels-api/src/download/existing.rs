@@ -0,0 +1,162 @@
+/// What a download does when a file already exists at its target path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExistingFilePolicy {
+    /// Keep the existing file without any request
+    Skip,
+    /// Download the file again from the start
+    Overwrite,
+    /// Treat the existing file as a partial download and append the rest (default)
+    ///
+    /// A file the server reports as complete, by refusing a range that starts at its end,
+    /// is kept as it is.
+    #[default]
+    Resume,
+    /// Keep the existing file and download to `<stem>_2.<ext>`, `<stem>_3.<ext>`, ...
+    RenameWithSuffix,
+    /// Keep the existing file if it matches the manifest, or the size announced by the
+    /// server in response to a `HEAD` request when it is not in the manifest, otherwise
+    /// download it again
+    VerifyThenSkip,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tokio::fs;
+
+    use super::*;
+    use crate::download::format::MediaKind;
+    use crate::download::DownloadTarget;
+    use crate::test_support::{
+        manager, range_not_satisfiable, response, serve, serve_logged, temp_dir, PNG,
+    };
+
+    fn target(url: String) -> DownloadTarget {
+        DownloadTarget {
+            media_id: 1,
+            quality: "original".to_string(),
+            url,
+            file_name: PathBuf::from("photo_1.png"),
+            kind: MediaKind::Image,
+            size: None,
+            attribution: None,
+        }
+    }
+
+    /// Download over an existing `photo_1.png`, return the path and content of the result
+    async fn download(
+        policy: ExistingFilePolicy,
+        existing: &[u8],
+        name: &str,
+    ) -> (PathBuf, Vec<u8>) {
+        let url = serve(vec![response("200 OK", PNG); 2]).await;
+        download_from(url, policy, existing, name).await
+    }
+
+    async fn download_from(
+        url: String,
+        policy: ExistingFilePolicy,
+        existing: &[u8],
+        name: &str,
+    ) -> (PathBuf, Vec<u8>) {
+        let dir = temp_dir(name);
+        fs::write(dir.join("photo_1.png"), existing).await.unwrap();
+
+        let manager = manager().with_existing_file_policy(policy);
+        let path = manager.download_file(&target(url), &dir, None).await.unwrap();
+        let data = fs::read(&path).await.unwrap();
+        if path != dir.join("photo_1.png") {
+            assert_eq!(fs::read(dir.join("photo_1.png")).await.unwrap(), existing);
+        }
+
+        fs::remove_dir_all(&dir).await.unwrap();
+        (path.strip_prefix(&dir).unwrap().to_path_buf(), data)
+    }
+
+    #[tokio::test]
+    async fn test_skip_keeps_the_file() {
+        let (path, data) = download(ExistingFilePolicy::Skip, b"old", "policy-skip").await;
+        assert_eq!(path, PathBuf::from("photo_1.png"));
+        assert_eq!(data, b"old");
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_replaces_the_file() {
+        let (path, data) =
+            download(ExistingFilePolicy::Overwrite, b"old", "policy-overwrite").await;
+        assert_eq!(path, PathBuf::from("photo_1.png"));
+        assert_eq!(data, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_rename_with_suffix_keeps_both() {
+        let (path, data) =
+            download(ExistingFilePolicy::RenameWithSuffix, b"old", "policy-rename").await;
+        assert_eq!(path, PathBuf::from("photo_1_2.png"));
+        assert_eq!(data, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_resume_appends_to_the_file() {
+        let (_, data) = download(ExistingFilePolicy::Resume, &PNG[..8], "policy-resume").await;
+        assert_eq!(data, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_resume_keeps_a_complete_file() {
+        let url = serve(vec![range_not_satisfiable(PNG.len() as u64)]).await;
+        let (_, data) =
+            download_from(url, ExistingFilePolicy::Resume, PNG, "policy-resume-complete").await;
+        assert_eq!(data, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_verify_then_skip_compares_the_size() {
+        let same_size = vec![b'x'; PNG.len()];
+        let (url, requests) = serve_logged(vec![response("200 OK", PNG)]).await;
+        let (_, data) =
+            download_from(url, ExistingFilePolicy::VerifyThenSkip, &same_size, "policy-verify")
+                .await;
+        assert_eq!(data, same_size);
+        assert_eq!(*requests.lock().unwrap(), ["HEAD /photo.png HTTP/1.1"]);
+
+        let (_, data) =
+            download(ExistingFilePolicy::VerifyThenSkip, b"truncated", "policy-reverify").await;
+        assert_eq!(data, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_renamed_downloads_are_found() {
+        let dir = temp_dir("policy-renamed");
+        // A `.jpg` URL that served PNG content was saved as `photo_1.png`
+        fs::write(dir.join("photo_1.png"), PNG).await.unwrap();
+        let mut target = target("http://127.0.0.1:1/photo.jpg".to_string());
+        target.file_name = PathBuf::from("photo_1.jpg");
+        target.size = Some(PNG.len() as u64);
+
+        for policy in [ExistingFilePolicy::Skip, ExistingFilePolicy::VerifyThenSkip] {
+            let manager = manager().with_existing_file_policy(policy);
+            let path = manager.download_file(&target, &dir, None).await.unwrap();
+            assert_eq!(path, dir.join("photo_1.png"));
+        }
+        assert!(!dir.join("photo_1.jpg").exists());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_then_skip_falls_back_to_the_api_size() {
+        let dir = temp_dir("policy-verify-offline");
+        fs::write(dir.join("photo_1.png"), PNG).await.unwrap();
+
+        // Nothing listens on port 1, so the HEAD request fails
+        let mut target = target("http://127.0.0.1:1/photo.png".to_string());
+        target.size = Some(PNG.len() as u64);
+        let manager = manager().with_existing_file_policy(ExistingFilePolicy::VerifyThenSkip);
+        let path = manager.download_file(&target, &dir, None).await.unwrap();
+        assert_eq!(path, dir.join("photo_1.png"));
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
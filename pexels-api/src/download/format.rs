@@ -0,0 +1,287 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::PexelsError;
+
+use super::naming::extension_from_url;
+
+/// Number of leading bytes needed to recognise every supported format
+pub(crate) const SNIFF_LEN: usize = 16;
+
+/// Broad kind of media a download is expected to produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Video,
+}
+
+impl Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            MediaKind::Image => "image",
+            MediaKind::Video => "video",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Concrete file format of downloaded media
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Webp,
+    Avif,
+    Heic,
+    Tiff,
+    Mp4,
+    Mov,
+    Webm,
+}
+
+impl MediaFormat {
+    /// Every format
+    pub(crate) const ALL: &'static [MediaFormat] = &[
+        MediaFormat::Jpeg,
+        MediaFormat::Png,
+        MediaFormat::Gif,
+        MediaFormat::Webp,
+        MediaFormat::Avif,
+        MediaFormat::Heic,
+        MediaFormat::Tiff,
+        MediaFormat::Mp4,
+        MediaFormat::Mov,
+        MediaFormat::Webm,
+    ];
+
+    /// Canonical file extension without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "jpg",
+            MediaFormat::Png => "png",
+            MediaFormat::Gif => "gif",
+            MediaFormat::Webp => "webp",
+            MediaFormat::Avif => "avif",
+            MediaFormat::Heic => "heic",
+            MediaFormat::Tiff => "tiff",
+            MediaFormat::Mp4 => "mp4",
+            MediaFormat::Mov => "mov",
+            MediaFormat::Webm => "webm",
+        }
+    }
+
+    /// MIME type of the format
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "image/jpeg",
+            MediaFormat::Png => "image/png",
+            MediaFormat::Gif => "image/gif",
+            MediaFormat::Webp => "image/webp",
+            MediaFormat::Avif => "image/avif",
+            MediaFormat::Heic => "image/heic",
+            MediaFormat::Tiff => "image/tiff",
+            MediaFormat::Mp4 => "video/mp4",
+            MediaFormat::Mov => "video/quicktime",
+            MediaFormat::Webm => "video/webm",
+        }
+    }
+
+    /// Whether the format is an image or a video
+    pub fn kind(&self) -> MediaKind {
+        match self {
+            MediaFormat::Mp4 | MediaFormat::Mov | MediaFormat::Webm => MediaKind::Video,
+            _ => MediaKind::Image,
+        }
+    }
+
+    /// Recognise a format from a MIME type, parameters such as `charset` are ignored
+    pub fn from_mime_type(mime: &str) -> Option<Self> {
+        let essence = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "image/jpeg" | "image/jpg" | "image/pjpeg" => Some(MediaFormat::Jpeg),
+            "image/png" => Some(MediaFormat::Png),
+            "image/gif" => Some(MediaFormat::Gif),
+            "image/webp" => Some(MediaFormat::Webp),
+            "image/avif" => Some(MediaFormat::Avif),
+            "image/heic" | "image/heif" => Some(MediaFormat::Heic),
+            "image/tiff" => Some(MediaFormat::Tiff),
+            "video/mp4" => Some(MediaFormat::Mp4),
+            "video/quicktime" => Some(MediaFormat::Mov),
+            "video/webm" => Some(MediaFormat::Webm),
+            _ => None,
+        }
+    }
+
+    /// Recognise a format from a file extension, with or without the leading dot
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.trim_start_matches('.').to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" | "jpe" => Some(MediaFormat::Jpeg),
+            "png" => Some(MediaFormat::Png),
+            "gif" => Some(MediaFormat::Gif),
+            "webp" => Some(MediaFormat::Webp),
+            "avif" => Some(MediaFormat::Avif),
+            "heic" | "heif" => Some(MediaFormat::Heic),
+            "tif" | "tiff" => Some(MediaFormat::Tiff),
+            "mp4" | "m4v" => Some(MediaFormat::Mp4),
+            "mov" => Some(MediaFormat::Mov),
+            "webm" => Some(MediaFormat::Webm),
+            _ => None,
+        }
+    }
+
+    /// Recognise a format from the magic bytes at the start of a file
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return Some(MediaFormat::Jpeg);
+        }
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(MediaFormat::Png);
+        }
+        if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            return Some(MediaFormat::Gif);
+        }
+        if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+            return Some(MediaFormat::Webp);
+        }
+        if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+            return Some(MediaFormat::Tiff);
+        }
+        if bytes.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+            return Some(MediaFormat::Webm);
+        }
+        // ISO base media files: size, `ftyp`, major brand
+        if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+            return match &bytes[8..12] {
+                b"avif" | b"avis" => Some(MediaFormat::Avif),
+                b"heic" | b"heix" | b"hevc" | b"mif1" | b"msf1" => Some(MediaFormat::Heic),
+                b"qt  " => Some(MediaFormat::Mov),
+                _ => Some(MediaFormat::Mp4),
+            };
+        }
+        None
+    }
+}
+
+impl Display for MediaFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mime_type())
+    }
+}
+
+/// Work out the format of a response and reject anything that is not the expected media
+///
+/// Magic bytes win over the `Content-Type` header, which wins over the URL extension.
+/// `Ok(None)` means the format could not be determined but nothing contradicts `expected`.
+///
+/// # Arguments
+/// * `expected` - The kind of media that was requested
+/// * `content_type` - The `Content-Type` response header, if any
+/// * `url` - The requested URL
+/// * `head` - The first bytes of the body, empty when resuming a partial download
+pub(crate) fn detect_format(
+    expected: MediaKind,
+    content_type: Option<&str>,
+    url: &str,
+    head: &[u8],
+) -> Result<Option<MediaFormat>, PexelsError> {
+    let mismatch = |found: String| PexelsError::UnexpectedContentType {
+        expected: expected.to_string(),
+        found,
+    };
+
+    if let Some(content_type) = content_type {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        let essence = essence.to_ascii_lowercase();
+        if essence.starts_with("text/") || essence.ends_with("json") || essence.ends_with("xml") {
+            return Err(mismatch(essence));
+        }
+    }
+
+    if looks_like_document(head) {
+        return Err(mismatch("text document".to_string()));
+    }
+
+    let format = MediaFormat::from_magic_bytes(head)
+        .or_else(|| content_type.and_then(MediaFormat::from_mime_type))
+        .or_else(|| extension_from_url(url).as_deref().and_then(MediaFormat::from_extension));
+
+    match format {
+        Some(format) if format.kind() != expected => Err(mismatch(format.to_string())),
+        format => Ok(format),
+    }
+}
+
+/// Error pages and API errors start with markup or JSON rather than binary data
+fn looks_like_document(head: &[u8]) -> bool {
+    let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    matches!(head.iter().find(|b| !b.is_ascii_whitespace()), Some(b'<' | b'{' | b'['))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10, b'J', b'F', b'I', b'F'];
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const MP4: &[u8] = b"\0\0\0\x20ftypisom\0\0\x02\0";
+
+    #[test]
+    fn test_magic_bytes() {
+        assert_eq!(MediaFormat::from_magic_bytes(JPEG), Some(MediaFormat::Jpeg));
+        assert_eq!(MediaFormat::from_magic_bytes(PNG), Some(MediaFormat::Png));
+        assert_eq!(MediaFormat::from_magic_bytes(MP4), Some(MediaFormat::Mp4));
+        assert_eq!(MediaFormat::from_magic_bytes(b"\0\0\0\x14ftypqt  "), Some(MediaFormat::Mov));
+        assert_eq!(MediaFormat::from_magic_bytes(b"RIFF\0\0\0\0WEBPVP8 "), Some(MediaFormat::Webp));
+        assert_eq!(MediaFormat::from_magic_bytes(b"hello"), None);
+    }
+
+    #[test]
+    fn test_magic_bytes_win_over_headers() {
+        let url = "https://images.pexels.com/photos/1/photo.jpeg";
+        let format = detect_format(MediaKind::Image, Some("image/jpeg"), url, PNG).unwrap();
+        assert_eq!(format, Some(MediaFormat::Png));
+    }
+
+    #[test]
+    fn test_falls_back_to_content_type_and_url() {
+        let url = "https://videos.pexels.com/video-files/1/file.mp4";
+        let format = detect_format(MediaKind::Video, Some("video/quicktime"), url, &[]).unwrap();
+        assert_eq!(format, Some(MediaFormat::Mov));
+
+        let format = detect_format(MediaKind::Video, None, url, &[]).unwrap();
+        assert_eq!(format, Some(MediaFormat::Mp4));
+
+        let format = detect_format(MediaKind::Image, None, "https://example.com/x", &[]).unwrap();
+        assert_eq!(format, None);
+    }
+
+    #[test]
+    fn test_rejects_error_pages() {
+        let url = "https://images.pexels.com/photos/1/photo.jpeg";
+        let html = b"\n<!DOCTYPE html><html>";
+
+        assert!(matches!(
+            detect_format(MediaKind::Image, Some("text/html; charset=utf-8"), url, JPEG),
+            Err(PexelsError::UnexpectedContentType { .. })
+        ));
+        assert!(matches!(
+            detect_format(MediaKind::Image, Some("application/octet-stream"), url, html),
+            Err(PexelsError::UnexpectedContentType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_wrong_kind() {
+        let url = "https://videos.pexels.com/video-files/1/file.mp4";
+        assert_eq!(
+            detect_format(MediaKind::Video, None, url, JPEG),
+            Err(PexelsError::UnexpectedContentType {
+                expected: "video".to_string(),
+                found: "image/jpeg".to_string(),
+            })
+        );
+    }
+}
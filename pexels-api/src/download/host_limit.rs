@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
+
+/// Concurrency limits per host, e.g. fewer parallel transfers from `videos.pexels.com`
+/// than from `images.pexels.com`
+#[derive(Debug, Default)]
+pub(crate) struct HostLimits {
+    /// Limits of individual hosts
+    limits: HashMap<String, usize>,
+    /// Limit of every other host, unlimited if unset
+    default: Option<usize>,
+    /// One semaphore per host seen so far
+    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostLimits {
+    pub(crate) fn new(limits: HashMap<String, usize>, default: Option<usize>) -> Self {
+        Self { limits, default, semaphores: Mutex::default() }
+    }
+
+    /// Wait for a free slot of the host of `url`, `None` if the host is unlimited
+    pub(crate) async fn acquire(&self, url: &str) -> Option<OwnedSemaphorePermit> {
+        let host = Url::parse(url).ok()?.host_str()?.to_ascii_lowercase();
+        let limit = self.limits.get(&host).copied().or(self.default)?;
+
+        let semaphore = {
+            let mut semaphores = self.semaphores.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(
+                semaphores.entry(host).or_insert_with(|| Arc::new(Semaphore::new(limit.max(1)))),
+            )
+        };
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hosts_are_limited_separately() {
+        let limits = HostLimits::new(HashMap::from([("videos.pexels.com".to_string(), 1)]), None);
+
+        let video = limits.acquire("https://videos.pexels.com/a.mp4").await;
+        assert!(video.is_some());
+        let second = limits.acquire("https://VIDEOS.pexels.com/b.mp4");
+        assert!(tokio::time::timeout(Duration::from_millis(50), second).await.is_err());
+
+        // Other hosts are unlimited without a default
+        assert!(limits.acquire("https://images.pexels.com/a.jpg").await.is_none());
+
+        drop(video);
+        assert!(limits.acquire("https://videos.pexels.com/b.mp4").await.is_some());
+    }
+}
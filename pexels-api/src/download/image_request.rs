@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use url::Url;
+
+use crate::PexelsError;
+
+/// Maximum device pixel ratio accepted by the Pexels image CDN
+const MAX_DPR: f32 = 5.0;
+
+/// How the image CDN fits the photo into the requested box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fit {
+    /// Fill the box exactly, cropping what does not fit
+    #[default]
+    Crop,
+    /// Fit inside the box without cropping, keeping the aspect ratio
+    Clip,
+    /// Like `Clip`, but never upscale
+    Max,
+    /// Cover the box without cropping, keeping the aspect ratio
+    Min,
+    /// Stretch to the box, ignoring the aspect ratio
+    Scale,
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Fit::Crop => "crop",
+            Fit::Clip => "clip",
+            Fit::Max => "max",
+            Fit::Min => "min",
+            Fit::Scale => "scale",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// An exactly sized photo variant built from the original through the Pexels image CDN
+///
+/// The fixed [`ImageQuality`](super::ImageQuality) sizes come from `PhotoSources`; an
+/// `ImageRequest` instead sets the `auto`, `cs`, `w`, `h`, `fit` and `dpr` query parameters
+/// on `src.original` directly.
+///
+/// # Example
+/// ```rust
+/// use pexels_api::{Fit, ImageRequest};
+///
+/// let request = ImageRequest::new().width(300).height(200).fit(Fit::Crop).dpr(2.0);
+/// assert_eq!(
+///     request.url("https://images.pexels.com/photos/2014422/pexels-photo-2014422.jpeg").unwrap(),
+///     "https://images.pexels.com/photos/2014422/pexels-photo-2014422.jpeg?auto=compress&cs=tinysrgb&w=300&h=200&fit=crop&dpr=2"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRequest {
+    width: Option<u32>,
+    height: Option<u32>,
+    fit: Fit,
+    dpr: f32,
+    compress: bool,
+}
+
+impl Default for ImageRequest {
+    fn default() -> Self {
+        Self { width: None, height: None, fit: Fit::default(), dpr: 1.0, compress: true }
+    }
+}
+
+impl ImageRequest {
+    /// Creates a new [`ImageRequest`], compressed with a device pixel ratio of 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width in CSS pixels
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the height in CSS pixels
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Sets how the photo is fitted into the width and height
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Sets the device pixel ratio, the delivered image is `dpr` times the requested size
+    pub fn dpr(mut self, dpr: f32) -> Self {
+        self.dpr = dpr;
+        self
+    }
+
+    /// Enables or disables CDN compression (`auto=compress&cs=tinysrgb`)
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Builds the CDN URL for a photo from its `src.original` URL
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] for a zero width or height or a device pixel
+    /// ratio outside `(0, 5]`, and [`PexelsError::ParseError`] for an invalid URL.
+    pub fn url(&self, original: &str) -> Result<String, PexelsError> {
+        self.validate()?;
+
+        let mut url = Url::parse(original)?;
+        url.set_query(None);
+
+        {
+            let mut query = url.query_pairs_mut();
+            if self.compress {
+                query.append_pair("auto", "compress").append_pair("cs", "tinysrgb");
+            }
+            if let Some(width) = self.width {
+                query.append_pair("w", &width.to_string());
+            }
+            if let Some(height) = self.height {
+                query.append_pair("h", &height.to_string());
+            }
+            if self.width.is_some() || self.height.is_some() {
+                query.append_pair("fit", &self.fit.to_string());
+            }
+            if self.dpr != 1.0 {
+                query.append_pair("dpr", &self.dpr.to_string());
+            }
+        }
+
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+
+        Ok(url.into())
+    }
+
+    /// Short description used for the `{quality}` file name placeholder, e.g. `300x200@2x`
+    pub fn label(&self) -> String {
+        let side = |v: Option<u32>| v.map_or_else(|| "auto".to_string(), |v| v.to_string());
+        let mut label = format!("{}x{}", side(self.width), side(self.height));
+        if self.dpr != 1.0 {
+            label.push_str(&format!("@{}x", self.dpr));
+        }
+        label
+    }
+
+    fn validate(&self) -> Result<(), PexelsError> {
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err(PexelsError::InvalidParameter(
+                "Image width and height must be greater than 0".to_string(),
+            ));
+        }
+
+        if !(self.dpr > 0.0 && self.dpr <= MAX_DPR) {
+            return Err(PexelsError::InvalidParameter(format!(
+                "Image dpr must be in (0, {MAX_DPR}], got {}",
+                self.dpr
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "https://images.pexels.com/photos/1/pexels-photo-1.jpeg";
+
+    #[test]
+    fn test_replaces_existing_query() {
+        let url = ImageRequest::new().width(940).url(&format!("{ORIGINAL}?w=1880&auto=compress"));
+        assert_eq!(url.unwrap(), format!("{ORIGINAL}?auto=compress&cs=tinysrgb&w=940&fit=crop"));
+    }
+
+    #[test]
+    fn test_uncompressed_original() {
+        let url = ImageRequest::new().compress(false).url(ORIGINAL);
+        assert_eq!(url.unwrap(), ORIGINAL);
+    }
+
+    #[test]
+    fn test_fit_and_fractional_dpr() {
+        let request = ImageRequest::new().height(350).fit(Fit::Max).dpr(1.5);
+        assert_eq!(
+            request.url(ORIGINAL).unwrap(),
+            format!("{ORIGINAL}?auto=compress&cs=tinysrgb&h=350&fit=max&dpr=1.5")
+        );
+        assert_eq!(request.label(), "autox350@1.5x");
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(
+            ImageRequest::new().width(0).url(ORIGINAL),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            ImageRequest::new().dpr(6.0).url(ORIGINAL),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            ImageRequest::new().dpr(f32::NAN).url(ORIGINAL),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+    }
+}
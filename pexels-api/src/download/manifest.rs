@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::PexelsError;
+
+use super::checksum::{checksum_file, Checksum};
+use super::format::MediaKind;
+
+/// Name of the manifest file kept in the output directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Record of the files downloaded into an output directory
+///
+/// Entries are keyed by the path relative to the directory, with `/` as separator. Keys that
+/// would leave the directory, such as absolute paths or `..`, are dropped when loading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// A downloaded file in the [`Manifest`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Size in bytes
+    pub size: u64,
+    /// Digest of the content, absent when checksums were disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<Checksum>,
+    /// Whether the file is a photo or a video
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<MediaKind>,
+    /// Pexels id of the photo or video
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_id: Option<u64>,
+    /// Quality label the file was downloaded with, e.g. `large2x`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// URL the file was downloaded from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Completion time as seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downloaded_at: Option<u64>,
+}
+
+/// State of a manifest entry on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EntryStatus {
+    Verified,
+    Missing,
+    Corrupt,
+}
+
+impl Manifest {
+    /// Load the manifest of a directory, an absent manifest is empty
+    pub async fn load<P: AsRef<Path>>(dir: P) -> Result<Self, PexelsError> {
+        match fs::read(dir.as_ref().join(MANIFEST_FILE_NAME)).await {
+            Ok(bytes) => {
+                let mut manifest: Self = serde_json::from_slice(&bytes)?;
+                manifest.files.retain(|key, _| is_safe_key(key));
+                Ok(manifest)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the manifest into a directory, replacing the previous one atomically
+    pub async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<(), PexelsError> {
+        let path = dir.as_ref().join(MANIFEST_FILE_NAME);
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_vec_pretty(self)?).await?;
+        fs::rename(&temp, &path).await?;
+        Ok(())
+    }
+
+    /// Check every file in the manifest against its recorded size and checksum
+    pub async fn verify<P: AsRef<Path>>(&self, dir: P) -> Result<VerifyReport, PexelsError> {
+        let dir = dir.as_ref();
+        let mut report = VerifyReport::default();
+
+        for (key, entry) in &self.files {
+            let Some(path) = entry_path(dir, key) else { continue };
+            match entry.status(&path).await? {
+                EntryStatus::Verified => report.verified.push(path),
+                EntryStatus::Missing => report.missing.push(path),
+                EntryStatus::Corrupt => report.corrupt.push(path),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Find the entry of a photo or video downloaded with the given quality
+    pub fn find(
+        &self,
+        kind: MediaKind,
+        media_id: u64,
+        quality: &str,
+    ) -> Option<(&str, &ManifestEntry)> {
+        self.files
+            .iter()
+            .find(|(key, entry)| {
+                is_safe_key(key)
+                    && entry.kind == Some(kind)
+                    && entry.media_id == Some(media_id)
+                    && entry.quality.as_deref() == Some(quality)
+            })
+            .map(|(key, entry)| (key.as_str(), entry))
+    }
+}
+
+impl ManifestEntry {
+    /// Compare the file at `path` with the recorded size and checksum
+    pub(crate) async fn status(&self, path: &Path) -> std::io::Result<EntryStatus> {
+        let actual = match &self.checksum {
+            Some(checksum) => match checksum_file(path, checksum.algorithm).await {
+                Ok((size, actual)) => (size, Some(actual)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Ok(EntryStatus::Missing)
+                }
+                Err(e) => return Err(e),
+            },
+            None => match fs::metadata(path).await {
+                Ok(metadata) => (metadata.len(), None),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Ok(EntryStatus::Missing)
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        if actual == (self.size, self.checksum.clone()) {
+            Ok(EntryStatus::Verified)
+        } else {
+            Ok(EntryStatus::Corrupt)
+        }
+    }
+}
+
+/// Outcome of [`Manifest::verify`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Files that match the manifest
+    pub verified: Vec<PathBuf>,
+    /// Files listed in the manifest but absent
+    pub missing: Vec<PathBuf>,
+    /// Files whose size or checksum differs from the manifest
+    pub corrupt: Vec<PathBuf>,
+}
+
+impl VerifyReport {
+    /// Whether every file matched
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Manifest key of a path relative to the output directory
+pub(crate) fn manifest_key(relative: &Path) -> String {
+    relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => segment.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Whether `key` is a relative path that stays inside the output directory
+fn is_safe_key(key: &str) -> bool {
+    let path = Path::new(key);
+    !key.is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Path of the file recorded under `key` in `dir`, `None` if the key would leave `dir`
+pub(crate) fn entry_path(dir: &Path, key: &str) -> Option<PathBuf> {
+    is_safe_key(key).then(|| dir.join(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::download::checksum::ChecksumAlgorithm;
+    use crate::test_support::temp_dir;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_save_load_and_verify() {
+        let dir = temp_dir("manifest");
+        fs::create_dir_all(dir.join("photos")).await.unwrap();
+        fs::write(dir.join("photos/1.jpg"), b"abc").await.unwrap();
+        fs::write(dir.join("2.jpg"), b"abc").await.unwrap();
+
+        let (size, checksum) =
+            checksum_file(&dir.join("photos/1.jpg"), ChecksumAlgorithm::Sha256).await.unwrap();
+        let mut manifest = Manifest::default();
+        let entry = ManifestEntry {
+            size,
+            checksum: Some(checksum),
+            kind: Some(MediaKind::Image),
+            media_id: Some(1),
+            quality: Some("original".to_string()),
+            url: None,
+            downloaded_at: None,
+        };
+        manifest.files.insert(manifest_key(Path::new("photos/1.jpg")), entry.clone());
+        manifest.files.insert("2.jpg".to_string(), entry.clone());
+        manifest.files.insert("3.jpg".to_string(), entry);
+        manifest.save(&dir).await.unwrap();
+
+        fs::write(dir.join("2.jpg"), b"abd").await.unwrap();
+
+        let manifest = Manifest::load(&dir).await.unwrap();
+        let report = manifest.verify(&dir).await.unwrap();
+        assert_eq!(report.verified, vec![dir.join("photos/1.jpg")]);
+        assert_eq!(report.corrupt, vec![dir.join("2.jpg")]);
+        assert_eq!(report.missing, vec![dir.join("3.jpg")]);
+        assert!(!report.is_ok());
+        assert!(manifest.find(MediaKind::Image, 1, "original").is_some());
+        assert!(manifest.find(MediaKind::Video, 1, "original").is_none());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_keys_outside_the_directory_are_dropped() {
+        let dir = temp_dir("manifest-keys");
+        let entry = json!({ "size": 3 });
+        let files = json!({ "1.jpg": entry, "../2.jpg": entry, "/tmp/3.jpg": entry, "": entry });
+        let manifest = json!({ "files": files }).to_string();
+        fs::write(dir.join(MANIFEST_FILE_NAME), manifest).await.unwrap();
+
+        let manifest = Manifest::load(&dir).await.unwrap();
+        assert_eq!(manifest.files.keys().collect::<Vec<_>>(), vec!["1.jpg"]);
+        assert_eq!(entry_path(&dir, "a/1.jpg"), Some(dir.join("a/1.jpg")));
+        assert_eq!(entry_path(&dir, "a/../../1.jpg"), None);
+        assert_eq!(entry_path(&dir, "./1.jpg"), None);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,1491 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::stream::StreamExt;
+use reqwest::Client;
+use tokio::fs;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::models::{Photo, Video, VideoFile};
+use crate::PexelsError;
+
+mod attribution;
+mod builder;
+mod checksum;
+mod collection;
+mod embed;
+mod existing;
+mod format;
+mod host_limit;
+mod image_request;
+mod manifest;
+mod naming;
+mod pictures;
+mod preflight;
+mod rendition;
+mod report;
+mod stream;
+mod throttle;
+
+pub use attribution::{Attribution, SidecarFormat};
+pub use builder::DownloadManagerBuilder;
+pub use checksum::{Checksum, ChecksumAlgorithm};
+pub use collection::CollectionDownloadOptions;
+pub use embed::{embed_attribution, embed_attribution_bytes};
+pub use existing::ExistingFilePolicy;
+pub use format::{MediaFormat, MediaKind};
+pub use image_request::{Fit, ImageRequest};
+pub use manifest::{Manifest, ManifestEntry, VerifyReport, MANIFEST_FILE_NAME};
+pub use naming::{FileNameTemplate, NameContext, DEFAULT_PHOTO_TEMPLATE, DEFAULT_VIDEO_TEMPLATE};
+pub use preflight::{OverflowPolicy, SizeEstimate};
+pub use rendition::{Rendition, RenditionSelector};
+pub use report::{BatchReport, DownloadFailure, DownloadedFile};
+pub use stream::MediaStream;
+pub use throttle::BandwidthLimiter;
+
+use attribution::{remove_sidecars, write_sidecars};
+use checksum::{checksum_file, Hasher};
+use host_limit::HostLimits;
+use manifest::{entry_path, manifest_key, EntryStatus};
+use naming::{existing_file, extension_from_url, free_path, UniqueNames};
+
+/// Picture quality enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageQuality {
+    Original,
+    Large2x,
+    Large,
+    Medium,
+    Small,
+    Portrait,
+    Landscape,
+    Tiny,
+}
+
+impl Display for ImageQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ImageQuality::Original => "original",
+            ImageQuality::Large2x => "large2x",
+            ImageQuality::Large => "large",
+            ImageQuality::Medium => "medium",
+            ImageQuality::Small => "small",
+            ImageQuality::Portrait => "portrait",
+            ImageQuality::Landscape => "landscape",
+            ImageQuality::Tiny => "tiny",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Video quality enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoQuality {
+    HD,
+    SD,
+    Tiny,
+}
+
+impl Display for VideoQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            VideoQuality::HD => "hd",
+            VideoQuality::SD => "sd",
+            VideoQuality::Tiny => "tiny",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// The type of progress callback function
+pub type ProgressCallback = fn(current: u64, total: u64);
+
+/// Default number of times a failed request or interrupted transfer is retried
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Result type alias
+type Result<T> = std::result::Result<T, PexelsError>;
+
+#[derive(Clone)]
+pub struct DownloadManager {
+    client: Client,
+    max_concurrent: usize,
+    /// How often a failed request or interrupted transfer is retried
+    max_retries: u32,
+    /// Limit shared by all transfers of this manager
+    bandwidth: Arc<BandwidthLimiter>,
+    /// Limit applied to each transfer individually
+    per_download: Arc<BandwidthLimiter>,
+    /// Relative path template for photos
+    photo_template: FileNameTemplate,
+    /// Relative path template for videos
+    video_template: FileNameTemplate,
+    /// Algorithm of the checksums recorded in the manifest, `None` disables them
+    checksum: Option<ChecksumAlgorithm>,
+    /// Keep a manifest in the output directory and skip completed downloads
+    manifest: bool,
+    /// Remove files of media that are no longer part of a batch
+    prune: bool,
+    /// Attribution sidecars written next to every file
+    sidecars: Vec<SidecarFormat>,
+    /// Write the attribution into the metadata of downloaded JPEG and PNG files
+    embed_metadata: bool,
+    /// What to do with a file that already exists at the target path
+    existing_files: ExistingFilePolicy,
+    /// Maximum number of bytes this manager and its clones may receive
+    byte_budget: Option<u64>,
+    /// Bytes received so far, shared by all clones
+    bytes_used: Arc<AtomicU64>,
+    /// Check batches against the free disk space and the byte budget before they start
+    space_check: Option<OverflowPolicy>,
+    /// Concurrent transfers allowed per host
+    host_limits: Arc<HostLimits>,
+    /// Serializes manifest updates of concurrent downloads
+    manifest_lock: Arc<Mutex<()>>,
+}
+
+/// A batch entry: media kind and id for the report, and what to download or why it can't be
+type BatchItem = (MediaKind, u64, Result<DownloadTarget>);
+
+/// Everything needed to download one media file
+struct DownloadTarget {
+    media_id: u64,
+    /// Label for the manifest and the `{quality}` placeholder
+    quality: String,
+    url: String,
+    /// Path relative to the output directory
+    file_name: PathBuf,
+    kind: MediaKind,
+    /// Size announced by the API, if known
+    size: Option<u64>,
+    /// Credit for the sidecars, `None` for plain URLs
+    attribution: Option<Attribution>,
+}
+
+impl DownloadManager {
+    /// Create a new 'DownloadManager' and specify the maximum number of concurrent downloads
+    /// Connecting times out after 30 seconds and a stalled transfer after 60 seconds, there
+    /// is no limit on the total duration. Use [`DownloadManager::builder`] to tune these.
+    ///
+    /// # Arguments
+    /// * `max_concurrent` - Maximum number of concurrent downloads
+    pub fn new(max_concurrent: usize) -> Self {
+        let client = DownloadManagerBuilder::new().client().unwrap_or_default();
+        Self::with_client(client, max_concurrent)
+    }
+
+    /// Creates a [`DownloadManagerBuilder`] for timeouts, per-host limits and HTTP/2 options
+    pub fn builder() -> DownloadManagerBuilder {
+        DownloadManagerBuilder::new()
+    }
+
+    /// Create a 'DownloadManager' with a custom 'Client'
+    pub fn with_client(client: Client, max_concurrent: usize) -> Self {
+        Self {
+            client,
+            max_concurrent,
+            max_retries: DEFAULT_MAX_RETRIES,
+            bandwidth: Arc::new(BandwidthLimiter::unlimited()),
+            per_download: Arc::new(BandwidthLimiter::unlimited()),
+            photo_template: DEFAULT_PHOTO_TEMPLATE.parse().expect("valid default template"),
+            video_template: DEFAULT_VIDEO_TEMPLATE.parse().expect("valid default template"),
+            checksum: None,
+            manifest: false,
+            prune: false,
+            sidecars: Vec::new(),
+            embed_metadata: false,
+            existing_files: ExistingFilePolicy::default(),
+            byte_budget: None,
+            bytes_used: Arc::new(AtomicU64::new(0)),
+            space_check: None,
+            host_limits: Arc::new(HostLimits::default()),
+            manifest_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Set the template used to name downloaded photos, see [`FileNameTemplate`]
+    ///
+    /// The default is [`DEFAULT_PHOTO_TEMPLATE`].
+    pub fn with_photo_template(mut self, template: FileNameTemplate) -> Self {
+        self.photo_template = template;
+        self
+    }
+
+    /// Set the template used to name downloaded videos, see [`FileNameTemplate`]
+    ///
+    /// The default is [`DEFAULT_VIDEO_TEMPLATE`].
+    pub fn with_video_template(mut self, template: FileNameTemplate) -> Self {
+        self.video_template = template;
+        self
+    }
+
+    /// Set how often a failed request or interrupted transfer is retried
+    ///
+    /// Connection errors, timeouts, `408`, `429` and `5xx` responses are retried with
+    /// exponential backoff, interrupted transfers continue where they stopped. The default
+    /// is 3.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Compute a checksum of every downloaded file and record it in the manifest
+    ///
+    /// Enables the manifest, see [`DownloadManager::with_manifest`]. Recorded files can be
+    /// rechecked later with [`DownloadManager::verify`].
+    pub fn with_checksum(mut self, algorithm: ChecksumAlgorithm) -> Self {
+        self.checksum = Some(algorithm);
+        self.manifest = true;
+        self
+    }
+
+    /// Keep a manifest of the downloaded files in the output directory
+    ///
+    /// The manifest is kept as [`MANIFEST_FILE_NAME`] and maps every file to its media id,
+    /// quality, source URL, size, checksum and download time. Downloads of media already in
+    /// the manifest are skipped as long as the file still matches, missing or corrupt files
+    /// are fetched again, which turns repeated batch downloads into an idempotent mirror.
+    pub fn with_manifest(mut self, enabled: bool) -> Self {
+        self.manifest = enabled;
+        self
+    }
+
+    /// After a batch, delete files of media that are not part of the batch
+    ///
+    /// Only applies with the manifest enabled and only to files of the batch's media kind
+    /// that are recorded in the manifest.
+    pub fn with_prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Write an attribution sidecar in this format next to every downloaded file
+    ///
+    /// Can be called once per format to get both JSON and XMP sidecars. The sidecar holds
+    /// the photographer's name, URL and id, the Pexels page, `alt`, `avg_color`, the
+    /// dimensions, the downloaded rendition and the download date, see [`Attribution`].
+    pub fn with_sidecar(mut self, format: SidecarFormat) -> Self {
+        if !self.sidecars.contains(&format) {
+            self.sidecars.push(format);
+        }
+        self
+    }
+
+    /// Write the attribution into the metadata of every downloaded JPEG and PNG photo
+    ///
+    /// See [`embed_attribution`] for the properties written. Other formats are left as is.
+    pub fn with_embedded_metadata(mut self, enabled: bool) -> Self {
+        self.embed_metadata = enabled;
+        self
+    }
+
+    /// Set what happens when a file already exists at the target path
+    ///
+    /// Applies to single and batch downloads alike. The default is
+    /// [`ExistingFilePolicy::Resume`]. With the manifest enabled, media it records as intact
+    /// are skipped before the policy is consulted, except with
+    /// [`ExistingFilePolicy::Overwrite`].
+    pub fn with_existing_file_policy(mut self, policy: ExistingFilePolicy) -> Self {
+        self.existing_files = policy;
+        self
+    }
+
+    /// Limit the total number of bytes this manager and its clones download
+    ///
+    /// A download that would exceed the budget fails with [`PexelsError::BudgetExceeded`].
+    /// Together with [`DownloadManager::with_space_check`] batches are checked against the
+    /// remaining budget before they start.
+    pub fn with_byte_budget(mut self, max_total_bytes: u64) -> Self {
+        self.byte_budget = Some(max_total_bytes);
+        self
+    }
+
+    /// Estimate the size of every batch before it starts and compare it with the free space
+    /// of the output directory's filesystem and the remaining byte budget
+    ///
+    /// Sizes come from the video files' `size` or from HEAD requests, files already on disk
+    /// only count with their missing bytes. Batches that would overflow are handled
+    /// according to the [`OverflowPolicy`].
+    pub fn with_space_check(mut self, policy: OverflowPolicy) -> Self {
+        self.space_check = Some(policy);
+        self
+    }
+
+    /// Limit the combined throughput of all concurrent downloads
+    ///
+    /// # Arguments
+    /// * `bytes_per_second` - Maximum bytes per second across all transfers
+    pub fn with_bandwidth_limit(self, bytes_per_second: u64) -> Self {
+        self.set_bandwidth_limit(Some(bytes_per_second));
+        self
+    }
+
+    /// Limit the throughput of every single download
+    ///
+    /// # Arguments
+    /// * `bytes_per_second` - Maximum bytes per second for each transfer
+    pub fn with_per_download_limit(self, bytes_per_second: u64) -> Self {
+        self.set_per_download_limit(Some(bytes_per_second));
+        self
+    }
+
+    /// Change the global bandwidth limit at runtime, `None` removes the limit
+    ///
+    /// Running downloads pick up the new limit with their next chunk.
+    pub fn set_bandwidth_limit(&self, bytes_per_second: Option<u64>) {
+        self.bandwidth.set_limit(bytes_per_second);
+    }
+
+    /// Change the per-download bandwidth limit at runtime, `None` removes the limit
+    pub fn set_per_download_limit(&self, bytes_per_second: Option<u64>) {
+        self.per_download.set_limit(bytes_per_second);
+    }
+
+    /// The current global bandwidth limit in bytes per second
+    pub fn bandwidth_limit(&self) -> Option<u64> {
+        self.bandwidth.limit()
+    }
+
+    /// The current per-download bandwidth limit in bytes per second
+    pub fn per_download_limit(&self) -> Option<u64> {
+        self.per_download.limit()
+    }
+
+    /// Download the photos from the given URL and save to the specified output directory
+    /// Resumable upload is supported
+    /// The file extension follows the detected image format, and responses that are not
+    /// images (e.g. an HTML error page) fail with [`PexelsError::UnexpectedContentType`]
+    ///
+    /// # Arguments
+    /// * `photo` - Photos to download
+    /// * `output_dir` - Output directory
+    /// * `quality` - Download quality
+    ///
+    /// # Returns
+    /// The path to download the file
+    pub async fn download_photo<P: AsRef<Path>>(
+        &self,
+        photo: &Photo,
+        output_dir: P,
+        quality: ImageQuality,
+    ) -> Result<PathBuf> {
+        let url = self.get_photo_url(photo, quality);
+        let target = self.photo_target(photo, &quality.to_string(), url, None);
+        self.download_file(&target, output_dir, None).await
+    }
+
+    /// Download an exactly sized variant of the photo through the Pexels image CDN
+    /// Resumable upload is supported
+    ///
+    /// # Arguments
+    /// * `photo` - Photos to download
+    /// * `output_dir` - Output directory
+    /// * `request` - Size, fit, device pixel ratio and compression of the variant
+    ///
+    /// # Returns
+    /// The path to download the file
+    pub async fn download_photo_sized<P: AsRef<Path>>(
+        &self,
+        photo: &Photo,
+        output_dir: P,
+        request: &ImageRequest,
+    ) -> Result<PathBuf> {
+        let url = request.url(&photo.src.original)?;
+        let target = self.photo_target(photo, &request.label(), url, None);
+        self.download_file(&target, output_dir, None).await
+    }
+
+    /// Download the video from the given URL and save to the specified output directory
+    /// Resumable upload is supported
+    /// The file extension follows the detected video format, and responses that are not
+    /// videos fail with [`PexelsError::UnexpectedContentType`]
+    ///
+    /// # Arguments
+    /// * `video` - Video to download
+    /// * `output_dir` - Output directory
+    /// * `quality` - Download quality
+    ///
+    /// # Returns
+    /// The path to download the file
+    pub async fn download_video<P: AsRef<Path>>(
+        &self,
+        video: &Video,
+        output_dir: P,
+        quality: VideoQuality,
+    ) -> Result<PathBuf> {
+        let target = self.video_target(video, &quality.into(), Some(quality.to_string()), None)?;
+        self.download_file(&target, output_dir, None).await
+    }
+
+    /// Download the video rendition chosen by a [`RenditionSelector`]
+    /// Resumable upload is supported
+    ///
+    /// # Arguments
+    /// * `video` - Video to download
+    /// * `output_dir` - Output directory
+    /// * `selector` - Rules for choosing one of the video files
+    ///
+    /// # Returns
+    /// The path to download the file
+    pub async fn download_video_with<P: AsRef<Path>>(
+        &self,
+        video: &Video,
+        output_dir: P,
+        selector: &RenditionSelector,
+    ) -> Result<PathBuf> {
+        let target = self.video_target(video, selector, None, None)?;
+        self.download_file(&target, output_dir, None).await
+    }
+
+    /// Recheck every file recorded in the manifest of `dir`
+    ///
+    /// # Returns
+    /// Which files match their recorded size and checksum, which are missing and which
+    /// are corrupt
+    pub async fn verify<P: AsRef<Path>>(&self, dir: P) -> Result<VerifyReport> {
+        Manifest::load(&dir).await?.verify(&dir).await
+    }
+
+    /// Open a byte stream of any media URL
+    ///
+    /// The stream shares the throttling, retry and resume logic of the file downloads.
+    ///
+    /// # Arguments
+    /// * `url` - Media URL
+    /// * `expected` - The kind of media the response must contain
+    pub async fn open_stream(&self, url: &str, expected: MediaKind) -> Result<MediaStream> {
+        self.open_stream_at(url, 0, expected, None).await
+    }
+
+    /// Open a byte stream of the photo
+    ///
+    /// # Arguments
+    /// * `photo` - Photo to stream
+    /// * `quality` - Download quality
+    pub async fn photo_stream(&self, photo: &Photo, quality: ImageQuality) -> Result<MediaStream> {
+        let url = self.get_photo_url(photo, quality);
+        self.open_stream(&url, MediaKind::Image).await
+    }
+
+    /// Open a byte stream of the video
+    ///
+    /// # Arguments
+    /// * `video` - Video to stream
+    /// * `quality` - Download quality
+    pub async fn video_stream(&self, video: &Video, quality: VideoQuality) -> Result<MediaStream> {
+        let target = self.video_target(video, &quality.into(), Some(quality.to_string()), None)?;
+        self.open_stream(&target.url, MediaKind::Video).await
+    }
+
+    /// Download the photo into any asynchronous writer, e.g. an upload or a socket
+    ///
+    /// The writer is flushed but not shut down.
+    ///
+    /// # Arguments
+    /// * `photo` - Photo to download
+    /// * `quality` - Download quality
+    /// * `writer` - Destination of the bytes
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// The number of bytes written
+    pub async fn download_photo_to_writer<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        photo: &Photo,
+        quality: ImageQuality,
+        writer: &mut W,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<u64> {
+        let url = self.get_photo_url(photo, quality);
+        let stream = self.open_stream_at(&url, 0, MediaKind::Image, progress_callback).await?;
+        Self::write_stream(stream, writer).await
+    }
+
+    /// Download the video into any asynchronous writer, e.g. an upload or a socket
+    ///
+    /// The writer is flushed but not shut down.
+    ///
+    /// # Arguments
+    /// * `video` - Video to download
+    /// * `quality` - Download quality
+    /// * `writer` - Destination of the bytes
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// The number of bytes written
+    pub async fn download_video_to_writer<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        video: &Video,
+        quality: VideoQuality,
+        writer: &mut W,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<u64> {
+        let target = self.video_target(video, &quality.into(), Some(quality.to_string()), None)?;
+        let stream =
+            self.open_stream_at(&target.url, 0, MediaKind::Video, progress_callback).await?;
+        Self::write_stream(stream, writer).await
+    }
+
+    /// Download the photo into memory
+    ///
+    /// # Arguments
+    /// * `photo` - Photo to download
+    /// * `quality` - Download quality
+    pub async fn fetch_photo_bytes(&self, photo: &Photo, quality: ImageQuality) -> Result<Vec<u8>> {
+        Self::collect_stream(self.photo_stream(photo, quality).await?).await
+    }
+
+    /// Download the video into memory
+    ///
+    /// # Arguments
+    /// * `video` - Video to download
+    /// * `quality` - Download quality
+    pub async fn fetch_video_bytes(&self, video: &Video, quality: VideoQuality) -> Result<Vec<u8>> {
+        Self::collect_stream(self.video_stream(video, quality).await?).await
+    }
+
+    async fn write_stream<W: AsyncWrite + Unpin + ?Sized>(
+        mut stream: MediaStream,
+        writer: &mut W,
+    ) -> Result<u64> {
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    async fn collect_stream(mut stream: MediaStream) -> Result<Vec<u8>> {
+        let capacity = stream.total_size().unwrap_or(0).min(64 * 1024 * 1024) as usize;
+        let mut buffer = Vec::with_capacity(capacity);
+        while let Some(chunk) = stream.next().await {
+            buffer.extend_from_slice(&chunk?);
+        }
+        Ok(buffer)
+    }
+
+    /// Download photos in batches
+    ///
+    /// # Arguments
+    /// * `photos` - A list of photos to download
+    /// * `output_dir` - Output directory
+    /// * `quality` - Download quality
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A list of files that have been successfully downloaded
+    pub async fn batch_download_photos<P: AsRef<Path>>(
+        &self,
+        photos: &[Photo],
+        output_dir: P,
+        quality: ImageQuality,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        let label = quality.to_string();
+        self.batch_download_photo_urls(
+            photos,
+            output_dir,
+            |photo| Ok((self.get_photo_url(photo, quality), label.clone())),
+            progress_callback,
+        )
+        .await
+    }
+
+    /// Download exactly sized photo variants in batches through the Pexels image CDN
+    ///
+    /// # Arguments
+    /// * `photos` - A list of photos to download
+    /// * `output_dir` - Output directory
+    /// * `request` - Size, fit, device pixel ratio and compression of the variants
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A list of files that have been successfully downloaded
+    pub async fn batch_download_photos_sized<P: AsRef<Path>>(
+        &self,
+        photos: &[Photo],
+        output_dir: P,
+        request: &ImageRequest,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        let label = request.label();
+        self.batch_download_photo_urls(
+            photos,
+            output_dir,
+            |photo| Ok((request.url(&photo.src.original)?, label.clone())),
+            progress_callback,
+        )
+        .await
+    }
+
+    /// Download photos in batches, `source` yields the URL and quality label of each photo
+    async fn batch_download_photo_urls<P, F>(
+        &self,
+        photos: &[Photo],
+        output_dir: P,
+        source: F,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>>
+    where
+        P: AsRef<Path>,
+        F: Fn(&Photo) -> Result<(String, String)>,
+    {
+        let output_dir = output_dir.as_ref();
+        let mut names = UniqueNames::default();
+
+        let items = photos
+            .iter()
+            .map(|photo| {
+                let target = source(photo).map(|(url, quality)| {
+                    let mut target = self.photo_target(photo, &quality, url, None);
+                    target.file_name = names.claim(target.file_name);
+                    target
+                });
+                (MediaKind::Image, photo.id.get(), target)
+            })
+            .collect();
+
+        let report = self.run_batch(items, output_dir, progress_callback).await?;
+        let ids = photos.iter().map(|photo| photo.id.get()).collect();
+        self.prune_batch(output_dir, MediaKind::Image, &ids).await?;
+        Ok(report.log_failures())
+    }
+
+    /// Download videos in batches
+    ///
+    /// # Arguments
+    /// * `videos` - A list of videos to download
+    /// * `output_dir` - Output directory
+    /// * `quality` - Download quality
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A list of files that have been successfully downloaded
+    pub async fn batch_download_videos<P: AsRef<Path>>(
+        &self,
+        videos: &[Video],
+        output_dir: P,
+        quality: VideoQuality,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        self.batch_download_video_renditions(
+            videos,
+            output_dir,
+            &quality.into(),
+            Some(quality.to_string()),
+            progress_callback,
+        )
+        .await
+    }
+
+    /// Download videos in batches, choosing each rendition with a [`RenditionSelector`]
+    ///
+    /// # Arguments
+    /// * `videos` - A list of videos to download
+    /// * `output_dir` - Output directory
+    /// * `selector` - Rules for choosing one of the video files
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A list of files that have been successfully downloaded
+    pub async fn batch_download_videos_with<P: AsRef<Path>>(
+        &self,
+        videos: &[Video],
+        output_dir: P,
+        selector: &RenditionSelector,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        self.batch_download_video_renditions(videos, output_dir, selector, None, progress_callback)
+            .await
+    }
+
+    async fn batch_download_video_renditions<P: AsRef<Path>>(
+        &self,
+        videos: &[Video],
+        output_dir: P,
+        selector: &RenditionSelector,
+        quality: Option<String>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<Vec<PathBuf>> {
+        let output_dir = output_dir.as_ref();
+        let mut names = UniqueNames::default();
+
+        let items = videos
+            .iter()
+            .map(|video| {
+                let target =
+                    self.video_target(video, selector, quality.clone(), None).map(|mut target| {
+                        target.file_name = names.claim(target.file_name);
+                        target
+                    });
+                (MediaKind::Video, video.id.get(), target)
+            })
+            .collect();
+
+        let report = self.run_batch(items, output_dir, progress_callback).await?;
+        let ids = videos.iter().map(|video| video.id.get()).collect();
+        self.prune_batch(output_dir, MediaKind::Video, &ids).await?;
+        Ok(report.log_failures())
+    }
+
+    /// Download the items concurrently, at most `max_concurrent` at a time
+    ///
+    /// Every item carries its media kind and id for the report, and either the target or
+    /// the reason it could not be downloaded. Fails only if the space check refuses the
+    /// batch.
+    async fn run_batch(
+        &self,
+        mut items: Vec<BatchItem>,
+        output_dir: &Path,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<BatchReport> {
+        self.preflight(&mut items, output_dir).await?;
+
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
+
+        let handles: Vec<_> = items
+            .into_iter()
+            .map(|(kind, media_id, target)| {
+                let permit = Arc::clone(&semaphore).acquire_owned();
+                let manager = self.clone();
+                let dir = output_dir.to_path_buf();
+
+                let handle = tokio::spawn(async move {
+                    let _permit = permit.await.map_err(|_| PexelsError::AsyncError)?;
+                    manager.download_target(&target?, &dir, progress_callback).await
+                });
+                (kind, media_id, handle)
+            })
+            .collect();
+
+        // Wait for all downloads to complete
+        let mut report = BatchReport::default();
+        for (kind, media_id, handle) in handles {
+            match handle.await {
+                Ok(Ok(file)) => report.downloaded.push(file),
+                Ok(Err(error)) => report.failed.push(DownloadFailure { kind, media_id, error }),
+                Err(_) => report.failed.push(DownloadFailure {
+                    kind,
+                    media_id,
+                    error: PexelsError::AsyncError,
+                }),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Download a single file and return its path, see [`Self::download_target`]
+    async fn download_file<P: AsRef<Path>>(
+        &self,
+        target: &DownloadTarget,
+        output_dir: P,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<PathBuf> {
+        let file = self.download_target(target, output_dir, progress_callback).await?;
+        Ok(file.path)
+    }
+
+    /// Download a single file
+    ///
+    /// The received size is checked against `Content-Length` and the size announced by the
+    /// API. With checksums enabled the digest is computed while streaming and recorded in
+    /// the manifest of `output_dir`.
+    ///
+    /// # Arguments
+    /// * `target` - What to download and where to, the extension of its file name is
+    ///   replaced when the downloaded content turns out to be a different format
+    /// * `output_dir` - Output directory
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// The path, size and checksum of the downloaded file
+    async fn download_target<P: AsRef<Path>>(
+        &self,
+        target: &DownloadTarget,
+        output_dir: P,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<DownloadedFile> {
+        let output_dir = output_dir.as_ref();
+        if self.manifest && self.existing_files != ExistingFilePolicy::Overwrite {
+            if let Some(path) = self.completed(output_dir, target).await? {
+                return self.keep_existing(path, target).await;
+            }
+        }
+
+        let mut path = output_dir.join(&target.file_name);
+
+        // Make sure the directory exists, templates may add subdirectories
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent).await?;
+            }
+        }
+
+        let mut range_start = 0;
+        if let Some((found, len)) = existing_file(&path, target.kind).await {
+            match self.existing_files {
+                ExistingFilePolicy::Skip => return self.keep_existing(found, target).await,
+                ExistingFilePolicy::Overwrite => {}
+                ExistingFilePolicy::Resume => (path, range_start) = (found, len),
+                ExistingFilePolicy::RenameWithSuffix => path = free_path(&found),
+                ExistingFilePolicy::VerifyThenSkip => match self
+                    .recorded(output_dir, &found)
+                    .await?
+                {
+                    Some(EntryStatus::Verified) => return self.keep_existing(found, target).await,
+                    Some(_) => path = found,
+                    None => {
+                        // Not in the manifest, compare with the size the server announces
+                        let size = self.remote_size(&target.url).await.or(target.size);
+                        if size == Some(len) {
+                            return self.keep_existing(found, target).await;
+                        }
+                        path = found;
+                    }
+                },
+            }
+        }
+
+        if self.remaining_budget() == Some(0) {
+            return Err(PexelsError::BudgetExceeded(self.byte_budget.unwrap_or_default()));
+        }
+
+        let mut stream =
+            self.open_stream_at(&target.url, range_start, target.kind, progress_callback).await?;
+
+        if let Some(format) = stream.format() {
+            let planned = path.extension().and_then(|ext| ext.to_str());
+            let keeps_name =
+                planned.map_or(true, |ext| MediaFormat::from_extension(ext) == Some(format));
+            if range_start == 0 && !keeps_name {
+                path.set_extension(format.extension());
+            }
+        }
+
+        // The part already on disk is part of the digest too
+        let mut hasher = self.checksum.map(Hasher::new);
+        if let (Some(hasher), true) = (&mut hasher, range_start > 0) {
+            hasher.update_from_file(&path).await?;
+        }
+
+        let mut file = if range_start > 0 {
+            fs::OpenOptions::new().append(true).open(&path).await?
+        } else {
+            fs::File::create(&path).await?
+        };
+
+        let mut size = range_start;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            self.consume_budget(chunk.len() as u64)?;
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&chunk);
+            }
+            file.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+        file.flush().await?;
+
+        for (source, expected) in [("Content-Length", stream.total_size()), ("API", target.size)] {
+            if let Some(expected) = expected.filter(|&expected| expected != size) {
+                return Err(PexelsError::IntegrityError(format!(
+                    "{}: received {size} bytes, {source} announced {expected}",
+                    path.display()
+                )));
+            }
+        }
+
+        let mut checksum = hasher.map(Hasher::finalize);
+        let mut attribution_error = None;
+        let embeddable = matches!(stream.format(), Some(MediaFormat::Jpeg | MediaFormat::Png));
+        if let Some(attribution) = target.attribution.as_ref().filter(|_| self.embed_metadata) {
+            if embeddable {
+                match embed_attribution(&path, attribution).await {
+                    Ok(()) => {
+                        // Embedding rewrites the file, the manifest must describe the final content
+                        size = fs::metadata(&path).await?.len();
+                        if let Some(algorithm) = self.checksum {
+                            checksum = Some(checksum_file(&path, algorithm).await?.1);
+                        }
+                    }
+                    // The media itself is complete and left untouched, so it is still recorded
+                    Err(error) => attribution_error = Some(error.to_string()),
+                }
+            }
+        }
+
+        if self.manifest {
+            let downloaded_at = SystemTime::now().duration_since(UNIX_EPOCH).ok();
+            let entry = ManifestEntry {
+                size,
+                checksum: checksum.clone(),
+                kind: Some(target.kind),
+                media_id: Some(target.media_id),
+                quality: Some(target.quality.clone()),
+                url: Some(target.url.clone()),
+                downloaded_at: downloaded_at.map(|elapsed| elapsed.as_secs()),
+            };
+            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
+            self.record(output_dir, manifest_key(relative), entry).await?;
+        }
+
+        if let Some(attribution) = &target.attribution {
+            write_sidecars(&path, attribution, &self.sidecars, true).await?;
+        }
+
+        Ok(DownloadedFile { path, size, checksum, attribution_error })
+    }
+
+    /// Keep a file that is already in place, adding missing sidecars
+    async fn keep_existing(
+        &self,
+        path: PathBuf,
+        target: &DownloadTarget,
+    ) -> Result<DownloadedFile> {
+        if let Some(attribution) = &target.attribution {
+            write_sidecars(&path, attribution, &self.sidecars, false).await?;
+        }
+        let (size, checksum) = match self.checksum {
+            Some(algorithm) => {
+                let (size, checksum) = checksum_file(&path, algorithm).await?;
+                (size, Some(checksum))
+            }
+            None => (fs::metadata(&path).await?.len(), None),
+        };
+        Ok(DownloadedFile { path, size, checksum, attribution_error: None })
+    }
+
+    /// State of the file at `path` if the manifest of `output_dir` records it
+    async fn recorded(&self, output_dir: &Path, path: &Path) -> Result<Option<EntryStatus>> {
+        if !self.manifest {
+            return Ok(None);
+        }
+        let manifest = Manifest::load(output_dir).await?;
+        let relative = path.strip_prefix(output_dir).unwrap_or(path);
+        match manifest.files.get(&manifest_key(relative)) {
+            Some(entry) => Ok(Some(entry.status(path).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Add or replace an entry in the manifest of `output_dir`
+    async fn record(&self, output_dir: &Path, key: String, entry: ManifestEntry) -> Result<()> {
+        // Concurrent downloads into the same directory must not lose each other's entries
+        let _guard = self.manifest_lock.lock().await;
+        let mut manifest = Manifest::load(output_dir).await?;
+        manifest.files.insert(key, entry);
+        manifest.save(output_dir).await
+    }
+
+    /// The path of the target if the manifest shows it was already downloaded intact
+    ///
+    /// A missing or corrupt file is dropped from the manifest, and from disk so it is not
+    /// resumed, so that it gets downloaded again.
+    async fn completed(
+        &self,
+        output_dir: &Path,
+        target: &DownloadTarget,
+    ) -> Result<Option<PathBuf>> {
+        let manifest = Manifest::load(output_dir).await?;
+        let Some((key, entry)) = manifest.find(target.kind, target.media_id, &target.quality)
+        else {
+            return Ok(None);
+        };
+
+        let Some(path) = entry_path(output_dir, key) else {
+            return Ok(None);
+        };
+        match entry.status(&path).await? {
+            EntryStatus::Verified => return Ok(Some(path)),
+            EntryStatus::Corrupt => fs::remove_file(&path).await?,
+            EntryStatus::Missing => {}
+        }
+
+        let key = key.to_string();
+        let _guard = self.manifest_lock.lock().await;
+        let mut manifest = Manifest::load(output_dir).await?;
+        manifest.files.remove(&key);
+        manifest.save(output_dir).await?;
+        Ok(None)
+    }
+
+    /// Delete the recorded files of `kind` whose media id is not in `keep`
+    async fn prune_manifest(
+        &self,
+        output_dir: &Path,
+        kind: MediaKind,
+        keep: &HashSet<u64>,
+    ) -> Result<()> {
+        let _guard = self.manifest_lock.lock().await;
+        let mut manifest = Manifest::load(output_dir).await?;
+
+        let stale: Vec<String> = manifest
+            .files
+            .iter()
+            .filter(|(_, entry)| {
+                entry.kind == Some(kind) && entry.media_id.is_some_and(|id| !keep.contains(&id))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        for key in stale {
+            // Keys that would leave the output directory are never deleted
+            let Some(path) = entry_path(output_dir, &key) else { continue };
+            match fs::remove_file(&path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+            remove_sidecars(&path).await?;
+            manifest.files.remove(&key);
+        }
+        manifest.save(output_dir).await
+    }
+
+    /// Prune the output directory after a batch if enabled
+    async fn prune_batch(
+        &self,
+        output_dir: &Path,
+        kind: MediaKind,
+        ids: &HashSet<u64>,
+    ) -> Result<()> {
+        if self.manifest && self.prune {
+            self.prune_manifest(output_dir, kind, ids).await?;
+        }
+        Ok(())
+    }
+
+    /// Get the photo URL
+    fn get_photo_url(&self, photo: &Photo, quality: ImageQuality) -> String {
+        match quality {
+            ImageQuality::Original => photo.src.original.clone(),
+            ImageQuality::Large2x => photo.src.large2x.clone(),
+            ImageQuality::Large => photo.src.large.clone(),
+            ImageQuality::Medium => photo.src.medium.clone(),
+            ImageQuality::Small => photo.src.small.clone(),
+            ImageQuality::Portrait => photo.src.portrait.clone(),
+            ImageQuality::Landscape => photo.src.landscape.clone(),
+            ImageQuality::Tiny => photo.src.tiny.clone(),
+        }
+    }
+
+    /// Build the download target of a photo
+    fn photo_target(
+        &self,
+        photo: &Photo,
+        quality: &str,
+        url: String,
+        collection: Option<&str>,
+    ) -> DownloadTarget {
+        DownloadTarget {
+            media_id: photo.id.get(),
+            quality: quality.to_string(),
+            file_name: self.photo_file_name(photo, quality, &url, collection),
+            attribution: Some(Attribution::from_photo(photo, &url, quality)),
+            url,
+            kind: MediaKind::Image,
+            size: None,
+        }
+    }
+
+    /// Select the rendition to download and render its relative path
+    ///
+    /// `quality` is used for the `{quality}` placeholder, the file's own label otherwise
+    fn video_target(
+        &self,
+        video: &Video,
+        selector: &RenditionSelector,
+        quality: Option<String>,
+        collection: Option<&str>,
+    ) -> Result<DownloadTarget> {
+        let file = selector.select(&video.video_files).map_err(|e| match e {
+            PexelsError::NoSuitableRendition(reason) => {
+                PexelsError::NoSuitableRendition(format!("video {}: {reason}", video.id))
+            }
+            e => e,
+        })?;
+        let quality = quality.unwrap_or_else(|| match (&file.quality, file.height) {
+            (Some(label), _) => label.as_str().to_ascii_lowercase(),
+            (None, Some(height)) => format!("{height}p"),
+            (None, None) => "unknown".to_string(),
+        });
+        Ok(DownloadTarget {
+            media_id: video.id.get(),
+            file_name: self.video_file_name(video, file, &quality, collection),
+            attribution: Some(Attribution::from_video(video, file, &quality)),
+            quality,
+            url: file.link.clone(),
+            kind: MediaKind::Video,
+            size: file.size,
+        })
+    }
+
+    /// Render the relative path of a photo from the photo template
+    fn photo_file_name(
+        &self,
+        photo: &Photo,
+        quality: &str,
+        url: &str,
+        collection: Option<&str>,
+    ) -> PathBuf {
+        let context = NameContext {
+            id: photo.id.get(),
+            kind: "photo".to_string(),
+            photographer: photo.photographer.clone(),
+            alt: photo.alt.clone(),
+            width: photo.width,
+            height: photo.height,
+            quality: quality.to_string(),
+            ext: extension_from_url(url).unwrap_or_else(|| "jpg".to_string()),
+            collection: collection.map(str::to_string),
+        };
+        self.photo_template.render(&context)
+    }
+
+    /// Render the relative path of a video from the video template
+    fn video_file_name(
+        &self,
+        video: &Video,
+        file: &VideoFile,
+        quality: &str,
+        collection: Option<&str>,
+    ) -> PathBuf {
+        let ext = file
+            .file_type
+            .as_str()
+            .strip_prefix("video/")
+            .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_string)
+            .or_else(|| extension_from_url(&file.link))
+            .unwrap_or_else(|| "mp4".to_string());
+
+        let context = NameContext {
+            id: video.id.get(),
+            kind: "video".to_string(),
+            photographer: video.user.name.clone(),
+            alt: None,
+            width: file.width.unwrap_or(video.width),
+            height: file.height.unwrap_or(video.height),
+            quality: quality.to_string(),
+            ext,
+            collection: collection.map(str::to_string),
+        };
+        self.video_template.render(&context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PhotoSources;
+    use crate::{PhotoId, UserId};
+    use tokio::test;
+
+    // Simulate the Photo data structure
+    fn mock_photo() -> Photo {
+        Photo {
+            id: PhotoId::new(1),
+            width: 800,
+            height: 600,
+            url: "https://www.pexels.com/photo/1".to_string(),
+            photographer: "Test Photographer".to_string(),
+            photographer_url: Some("https://www.pexels.com/photographer".to_string()),
+            photographer_id: Some(UserId::new(1)),
+            avg_color: Some("#FFFFFF".to_string()),
+            src: PhotoSources {
+                original: "https://images.pexels.com/photos/1/original.jpg".to_string(),
+                large2x: "https://images.pexels.com/photos/1/large2x.jpg".to_string(),
+                large: "https://images.pexels.com/photos/1/large.jpg".to_string(),
+                medium: "https://images.pexels.com/photos/1/medium.jpg".to_string(),
+                small: "https://images.pexels.com/photos/1/small.jpg".to_string(),
+                portrait: "https://images.pexels.com/photos/1/portrait.jpg".to_string(),
+                landscape: "https://images.pexels.com/photos/1/landscape.jpg".to_string(),
+                tiny: "https://images.pexels.com/photos/1/tiny.jpg".to_string(),
+                extra: Default::default(),
+            },
+            liked: false,
+            alt: Some("Test Photo".to_string()),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    async fn test_get_photo_url() {
+        let manager = DownloadManager::new(5);
+        let photo = mock_photo();
+
+        assert_eq!(
+            manager.get_photo_url(&photo, ImageQuality::Original),
+            "https://images.pexels.com/photos/1/original.jpg"
+        );
+        assert_eq!(
+            manager.get_photo_url(&photo, ImageQuality::Large2x),
+            "https://images.pexels.com/photos/1/large2x.jpg"
+        );
+    }
+
+    #[test]
+    async fn test_bandwidth_limits_are_adjustable() {
+        let manager =
+            DownloadManager::new(5).with_bandwidth_limit(1_000).with_per_download_limit(500);
+        assert_eq!(manager.bandwidth_limit(), Some(1_000));
+        assert_eq!(manager.per_download_limit(), Some(500));
+
+        // Clones handed to download tasks share the same limiters
+        let clone = manager.clone();
+        manager.set_bandwidth_limit(None);
+        assert_eq!(clone.bandwidth_limit(), None);
+    }
+
+    #[test]
+    async fn test_photo_file_name_template() {
+        let photo = mock_photo();
+        let url = photo.src.original.clone();
+
+        let manager = DownloadManager::new(5);
+        assert_eq!(
+            manager.photo_file_name(&photo, "original", &url, None),
+            PathBuf::from("photo_1.jpg")
+        );
+
+        let template = FileNameTemplate::new("{collection}/{id}_{quality}_{width}x{height}.{ext}")
+            .expect("valid template");
+        let manager = DownloadManager::new(5).with_photo_template(template);
+        assert_eq!(
+            manager.photo_file_name(&photo, "large2x", &url, Some("nature")),
+            PathBuf::from("nature/1_large2x_800x600.jpg")
+        );
+    }
+
+    #[test]
+    async fn test_checksum_is_recorded_and_verified() {
+        use crate::test_support::{response, serve, temp_dir, PNG};
+
+        let dir = temp_dir("checksum");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let manager = crate::test_support::manager().with_checksum(ChecksumAlgorithm::Sha256);
+        let target = DownloadTarget {
+            media_id: 1,
+            quality: "original".to_string(),
+            url,
+            file_name: PathBuf::from("photos/1.jpg"),
+            kind: MediaKind::Image,
+            size: None,
+            attribution: None,
+        };
+
+        let path = manager.download_file(&target, &dir, None).await.unwrap();
+        assert_eq!(path, dir.join("photos/1.png"));
+
+        let manifest = Manifest::load(&dir).await.unwrap();
+        let entry = &manifest.files["photos/1.png"];
+        assert_eq!(entry.size, PNG.len() as u64);
+        assert_eq!(entry.checksum.as_ref().map(|c| c.algorithm), Some(ChecksumAlgorithm::Sha256));
+        assert!(manager.verify(&dir).await.unwrap().is_ok());
+
+        fs::write(&path, b"tampered").await.unwrap();
+        assert_eq!(manager.verify(&dir).await.unwrap().corrupt, vec![path.clone()]);
+
+        // A corrupt file is fetched again from scratch
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let target = DownloadTarget { url, ..target };
+        assert_eq!(manager.download_file(&target, &dir, None).await.unwrap(), path);
+        assert_eq!(fs::read(&path).await.unwrap(), PNG);
+        assert!(manager.verify(&dir).await.unwrap().is_ok());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    async fn test_batch_report_has_checksums() {
+        use crate::test_support::{response, serve, temp_dir, PNG};
+
+        let dir = temp_dir("batch-checksum");
+        let url = serve(vec![response("200 OK", PNG); 2]).await;
+        let manager = crate::test_support::manager().with_checksum(ChecksumAlgorithm::Sha256);
+        let item = |id: u64| {
+            let target = DownloadTarget {
+                media_id: id,
+                quality: "original".to_string(),
+                url: url.clone(),
+                file_name: PathBuf::from(format!("{id}.png")),
+                kind: MediaKind::Image,
+                size: None,
+                attribution: None,
+            };
+            (MediaKind::Image, id, Ok(target))
+        };
+
+        let report = manager.run_batch(vec![item(1), item(2)], &dir, None).await.unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.paths(), vec![dir.join("1.png"), dir.join("2.png")]);
+        let (_, expected) =
+            checksum_file(&dir.join("1.png"), ChecksumAlgorithm::Sha256).await.unwrap();
+        for file in &report.downloaded {
+            assert_eq!(file.size, PNG.len() as u64);
+            assert_eq!(file.checksum.as_ref(), Some(&expected));
+        }
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    async fn test_manifest_sync_skips_and_prunes() {
+        use crate::test_support::{response, serve, temp_dir, PNG};
+
+        let dir = temp_dir("sync");
+        let url = serve(vec![response("200 OK", PNG), response("200 OK", PNG)]).await;
+        let photo = |id: u64| {
+            let mut photo = mock_photo();
+            photo.id = id.into();
+            photo.src.original = url.clone();
+            photo
+        };
+        let manager = crate::test_support::manager()
+            .with_manifest(true)
+            .with_prune(true)
+            .with_max_retries(0)
+            .with_sidecar(SidecarFormat::Json)
+            .with_sidecar(SidecarFormat::Xmp);
+
+        let mut paths = manager
+            .batch_download_photos(&[photo(1), photo(2)], &dir, ImageQuality::Original, None)
+            .await
+            .unwrap();
+        paths.sort();
+        assert_eq!(paths, vec![dir.join("photo_1.png"), dir.join("photo_2.png")]);
+
+        // The server is gone, so this only succeeds if nothing is downloaded again
+        let paths = manager
+            .batch_download_photos(&[photo(1)], &dir, ImageQuality::Original, None)
+            .await
+            .unwrap();
+        assert_eq!(paths, vec![dir.join("photo_1.png")]);
+        assert!(!dir.join("photo_2.png").exists());
+        assert!(!dir.join("photo_2.png.json").exists());
+        assert!(dir.join("photo_1.xmp").exists());
+
+        let sidecar = fs::read(dir.join("photo_1.png.json")).await.unwrap();
+        let attribution: Attribution = serde_json::from_slice(&sidecar).unwrap();
+        assert_eq!(attribution.photographer, "Test Photographer");
+        assert_eq!(attribution.source_url, url);
+        assert!(attribution.downloaded_at.is_some());
+
+        let manifest = Manifest::load(&dir).await.unwrap();
+        assert_eq!(manifest.files.keys().collect::<Vec<_>>(), vec!["photo_1.png"]);
+        let entry = &manifest.files["photo_1.png"];
+        assert_eq!(entry.media_id, Some(1));
+        assert_eq!(entry.quality.as_deref(), Some("original"));
+        assert!(entry.downloaded_at.is_some());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    async fn test_prune_stays_inside_the_output_dir() {
+        use crate::test_support::temp_dir;
+
+        let root = temp_dir("prune-escape");
+        let dir = root.join("out");
+        fs::create_dir_all(&dir).await.unwrap();
+        fs::write(root.join("victim.txt"), b"keep").await.unwrap();
+        let entry = ManifestEntry {
+            size: 4,
+            checksum: None,
+            kind: Some(MediaKind::Image),
+            media_id: Some(9),
+            quality: Some("original".to_string()),
+            url: None,
+            downloaded_at: None,
+        };
+        let mut manifest = Manifest::default();
+        manifest.files.insert("../victim.txt".to_string(), entry);
+        manifest.save(&dir).await.unwrap();
+
+        let manager = DownloadManager::new(1).with_manifest(true).with_prune(true);
+        manager.prune_manifest(&dir, MediaKind::Image, &HashSet::new()).await.unwrap();
+        assert_eq!(fs::read(root.join("victim.txt")).await.unwrap(), b"keep");
+
+        fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[test]
+    async fn test_size_mismatch_is_rejected() {
+        use crate::test_support::{response, serve, temp_dir, PNG};
+
+        let dir = temp_dir("size-mismatch");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let target = DownloadTarget {
+            media_id: 1,
+            quality: "original".to_string(),
+            url,
+            file_name: PathBuf::from("video_1.mp4"),
+            kind: MediaKind::Image,
+            size: Some(1),
+            attribution: None,
+        };
+
+        let result = crate::test_support::manager().download_file(&target, &dir, None).await;
+        assert!(matches!(result, Err(PexelsError::IntegrityError(_))));
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    async fn test_embedded_metadata_is_checksummed() {
+        use crate::test_support::{response, serve, temp_dir};
+
+        const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\
+            \0\0\0\x0dIHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0\x1f\x15\xc4\x89\
+            \0\0\0\0IEND\xae\x42\x60\x82";
+
+        let dir = temp_dir("embed");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let mut photo = mock_photo();
+        photo.src.original = url;
+        let manager = crate::test_support::manager()
+            .with_checksum(ChecksumAlgorithm::Sha256)
+            .with_embedded_metadata(true);
+
+        let path = manager.download_photo(&photo, &dir, ImageQuality::Original).await.unwrap();
+        let data = fs::read(&path).await.unwrap();
+        assert!(data.len() > PNG.len());
+        assert!(data.windows(17).any(|w| w == b"XML:com.adobe.xmp"));
+        assert!(manager.verify(&dir).await.unwrap().is_ok());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    async fn test_failed_embedding_keeps_the_download() {
+        use crate::test_support::{response, serve, temp_dir, PNG};
+
+        let dir = temp_dir("embed-failure");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let mut photo = mock_photo();
+        photo.src.original = url;
+        let manager =
+            crate::test_support::manager().with_manifest(true).with_embedded_metadata(true);
+
+        // The test PNG has a truncated header chunk, so embedding fails
+        let target = manager.photo_target(&photo, "original", photo.src.original.clone(), None);
+        let file = manager.download_target(&target, &dir, None).await.unwrap();
+        assert!(file.attribution_error.is_some());
+        assert_eq!(fs::read(&file.path).await.unwrap(), PNG);
+        let manifest = Manifest::load(&dir).await.unwrap();
+        assert_eq!(manifest.files.len(), 1);
+        assert!(manager.verify(&dir).await.unwrap().is_ok());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
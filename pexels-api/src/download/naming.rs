@@ -0,0 +1,456 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tokio::fs;
+use url::Url;
+
+use crate::PexelsError;
+
+use super::format::{MediaFormat, MediaKind};
+
+/// Default template for downloaded photos
+pub const DEFAULT_PHOTO_TEMPLATE: &str = "photo_{id}.{ext}";
+
+/// Default template for downloaded videos
+pub const DEFAULT_VIDEO_TEMPLATE: &str = "video_{id}.{ext}";
+
+/// Maximum length in bytes of a single rendered path segment
+const MAX_SEGMENT_LEN: usize = 200;
+
+/// Maximum length in characters of the `{slug-of-alt}` placeholder
+const MAX_SLUG_LEN: usize = 60;
+
+/// Placeholders supported in a [`FileNameTemplate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Id,
+    Kind,
+    Photographer,
+    SlugOfAlt,
+    Width,
+    Height,
+    Quality,
+    Ext,
+    Orientation,
+    Collection,
+}
+
+impl FromStr for Placeholder {
+    type Err = PexelsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "id" => Ok(Placeholder::Id),
+            "type" => Ok(Placeholder::Kind),
+            "photographer" => Ok(Placeholder::Photographer),
+            "slug-of-alt" => Ok(Placeholder::SlugOfAlt),
+            "width" => Ok(Placeholder::Width),
+            "height" => Ok(Placeholder::Height),
+            "quality" => Ok(Placeholder::Quality),
+            "ext" => Ok(Placeholder::Ext),
+            "orientation" => Ok(Placeholder::Orientation),
+            "collection" => Ok(Placeholder::Collection),
+            _ => Err(PexelsError::InvalidParameter(format!(
+                "Unknown file name placeholder: {{{s}}}"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// Values available to a [`FileNameTemplate`] when rendering the name of one asset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameContext {
+    /// Media id
+    pub id: u64,
+    /// `photo` or `video`
+    pub kind: String,
+    /// Photographer or video author name
+    pub photographer: String,
+    /// Alternative text of the photo, if any
+    pub alt: Option<String>,
+    /// Width of the media in pixels
+    pub width: u32,
+    /// Height of the media in pixels
+    pub height: u32,
+    /// Requested quality, e.g. `original` or `hd`
+    pub quality: String,
+    /// File extension without the leading dot
+    pub ext: String,
+    /// Collection the media was downloaded from
+    pub collection: Option<String>,
+}
+
+impl NameContext {
+    /// `landscape`, `portrait` or `square`, derived from the dimensions
+    pub fn orientation(&self) -> &'static str {
+        match self.width.cmp(&self.height) {
+            std::cmp::Ordering::Greater => "landscape",
+            std::cmp::Ordering::Less => "portrait",
+            std::cmp::Ordering::Equal => "square",
+        }
+    }
+}
+
+/// Template used to build the relative path of a downloaded file
+///
+/// Supported placeholders: `{id}`, `{type}`, `{photographer}`, `{slug-of-alt}`, `{width}`,
+/// `{height}`, `{quality}`, `{ext}`, `{orientation}` and `{collection}`. A `/` in the
+/// template creates a subdirectory; placeholder values never do. Every path segment is
+/// sanitized, and segments that render empty are dropped.
+///
+/// # Example
+/// ```rust
+/// use pexels_api::{FileNameTemplate, NameContext};
+/// use std::path::PathBuf;
+///
+/// let template = FileNameTemplate::new("{photographer}/{width}x{height}_{id}.{ext}").unwrap();
+/// let context = NameContext {
+///     id: 42,
+///     photographer: "Jane Doe".to_string(),
+///     width: 1920,
+///     height: 1080,
+///     ext: "jpg".to_string(),
+///     ..Default::default()
+/// };
+/// assert_eq!(template.render(&context), PathBuf::from("Jane Doe/1920x1080_42.jpg"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileNameTemplate {
+    segments: Vec<Vec<Part>>,
+}
+
+impl FileNameTemplate {
+    /// Parse a template
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] for unknown or unterminated placeholders
+    /// and for templates that do not produce a file name.
+    pub fn new(template: &str) -> Result<Self, PexelsError> {
+        let mut segments = Vec::new();
+
+        for raw in template.split(['/', '\\']) {
+            let mut parts = Vec::new();
+            let mut rest = raw;
+
+            while let Some(open) = rest.find('{') {
+                if open > 0 {
+                    parts.push(Part::Literal(rest[..open].to_string()));
+                }
+                let close = rest[open..].find('}').ok_or_else(|| {
+                    PexelsError::InvalidParameter(format!(
+                        "Unterminated placeholder in file name template: {template}"
+                    ))
+                })?;
+                parts.push(Part::Placeholder(rest[open + 1..open + close].parse()?));
+                rest = &rest[open + close + 1..];
+            }
+
+            if !rest.is_empty() {
+                parts.push(Part::Literal(rest.to_string()));
+            }
+
+            if !parts.is_empty() {
+                segments.push(parts);
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(PexelsError::InvalidParameter(
+                "File name template must not be empty".to_string(),
+            ));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the relative path for the given context
+    pub fn render(&self, context: &NameContext) -> PathBuf {
+        let mut path = PathBuf::new();
+
+        for parts in &self.segments {
+            let mut segment = String::new();
+            for part in parts {
+                match part {
+                    Part::Literal(text) => segment.push_str(text),
+                    Part::Placeholder(placeholder) => {
+                        segment.push_str(&Self::value(*placeholder, context))
+                    }
+                }
+            }
+
+            let segment = sanitize_segment(&segment);
+            if !segment.is_empty() {
+                path.push(segment);
+            }
+        }
+
+        if path.as_os_str().is_empty() {
+            path.push(format!("{}_{}", context.kind, context.id));
+        }
+
+        path
+    }
+
+    fn value(placeholder: Placeholder, context: &NameContext) -> String {
+        // Placeholder values must never introduce path separators
+        let value = match placeholder {
+            Placeholder::Id => context.id.to_string(),
+            Placeholder::Kind => context.kind.clone(),
+            Placeholder::Photographer => context.photographer.clone(),
+            Placeholder::SlugOfAlt => slugify(context.alt.as_deref().unwrap_or_default()),
+            Placeholder::Width => context.width.to_string(),
+            Placeholder::Height => context.height.to_string(),
+            Placeholder::Quality => context.quality.clone(),
+            Placeholder::Ext => context.ext.clone(),
+            Placeholder::Orientation => context.orientation().to_string(),
+            Placeholder::Collection => context.collection.clone().unwrap_or_default(),
+        };
+        value.replace(['/', '\\'], "_")
+    }
+}
+
+impl FromStr for FileNameTemplate {
+    type Err = PexelsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl Display for FileNameTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, parts) in self.segments.iter().enumerate() {
+            if index > 0 {
+                write!(f, "/")?;
+            }
+            for part in parts {
+                match part {
+                    Part::Literal(text) => write!(f, "{text}")?,
+                    Part::Placeholder(placeholder) => {
+                        let name = match placeholder {
+                            Placeholder::Id => "id",
+                            Placeholder::Kind => "type",
+                            Placeholder::Photographer => "photographer",
+                            Placeholder::SlugOfAlt => "slug-of-alt",
+                            Placeholder::Width => "width",
+                            Placeholder::Height => "height",
+                            Placeholder::Quality => "quality",
+                            Placeholder::Ext => "ext",
+                            Placeholder::Orientation => "orientation",
+                            Placeholder::Collection => "collection",
+                        };
+                        write!(f, "{{{name}}}")?
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Hands out unique paths within a batch by suffixing `_2`, `_3`, ... on collisions
+#[derive(Debug, Default)]
+pub(crate) struct UniqueNames {
+    used: HashSet<PathBuf>,
+}
+
+impl UniqueNames {
+    pub(crate) fn claim(&mut self, path: PathBuf) -> PathBuf {
+        if self.used.insert(path.clone()) {
+            return path;
+        }
+
+        (2..)
+            .map(|n| with_suffix(&path, n))
+            .find(|candidate| self.used.insert(candidate.clone()))
+            .expect("exhausted collision suffixes")
+    }
+}
+
+/// The first of `path`, `<stem>_2.<ext>`, `<stem>_3.<ext>`, ... that does not exist yet
+pub(crate) fn free_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    (2..)
+        .map(|n| with_suffix(path, n))
+        .find(|candidate| !candidate.exists())
+        .expect("exhausted collision suffixes")
+}
+
+/// The file downloaded earlier for `path` and its size
+///
+/// Downloads are renamed to the extension of the format they turn out to be, so besides `path`
+/// itself this also finds it with the extension of any other format of `kind`.
+pub(crate) async fn existing_file(path: &Path, kind: MediaKind) -> Option<(PathBuf, u64)> {
+    let renamed = MediaFormat::ALL
+        .iter()
+        .filter(|format| format.kind() == kind && path.extension().is_some())
+        .map(|format| path.with_extension(format.extension()));
+    for candidate in std::iter::once(path.to_path_buf()).chain(renamed) {
+        match fs::metadata(&candidate).await {
+            Ok(metadata) if metadata.is_file() => return Some((candidate, metadata.len())),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `path` with `_<n>` appended to the file stem
+fn with_suffix(path: &Path, n: u32) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}_{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{n}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Guess a file extension from the path of a URL, e.g. `jpeg` -> `jpg`
+pub(crate) fn extension_from_url(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let name = url.path_segments()?.next_back()?;
+    let ext = Path::new(name).extension()?.to_str()?.to_ascii_lowercase();
+
+    if ext.is_empty() || ext.len() > 5 || !ext.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    Some(if ext == "jpeg" { "jpg".to_string() } else { ext })
+}
+
+/// Lowercase ASCII slug: alphanumerics kept, everything else collapsed into `-`
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            if slug.len() >= MAX_SLUG_LEN {
+                break;
+            }
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Make a single path segment safe on all common file systems
+pub(crate) fn sanitize_segment(segment: &str) -> String {
+    let cleaned: String = segment
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let mut cleaned = cleaned.trim().trim_matches('.').trim().to_string();
+
+    if cleaned.len() > MAX_SEGMENT_LEN {
+        let mut end = MAX_SEGMENT_LEN;
+        while !cleaned.is_char_boundary(end) {
+            end -= 1;
+        }
+        cleaned.truncate(end);
+    }
+
+    // Windows reserves these device names regardless of extension
+    let stem = cleaned.split('.').next().unwrap_or_default().to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit());
+    if reserved {
+        cleaned.insert(0, '_');
+    }
+
+    cleaned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> NameContext {
+        NameContext {
+            id: 7,
+            kind: "photo".to_string(),
+            photographer: "Ana/Bob: \"Studio\"".to_string(),
+            alt: Some("A Red Fox, sitting in the Snow!".to_string()),
+            width: 1080,
+            height: 1920,
+            quality: "original".to_string(),
+            ext: "png".to_string(),
+            collection: None,
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let template =
+            FileNameTemplate::new("{slug-of-alt}_{width}x{height}_{orientation}.{ext}").unwrap();
+        assert_eq!(
+            template.render(&context()),
+            PathBuf::from("a-red-fox-sitting-in-the-snow_1080x1920_portrait.png")
+        );
+    }
+
+    #[test]
+    fn test_values_are_sanitized() {
+        let template = FileNameTemplate::new("{collection}/{photographer}/{id}.{ext}").unwrap();
+        assert_eq!(template.render(&context()), PathBuf::from("Ana_Bob_ _Studio_/7.png"));
+    }
+
+    #[test]
+    fn test_parent_segments_are_dropped() {
+        let template = FileNameTemplate::new("../{id}.{ext}").unwrap();
+        assert_eq!(template.render(&context()), PathBuf::from("7.png"));
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(matches!(
+            FileNameTemplate::new("{nope}.jpg"),
+            Err(PexelsError::InvalidParameter(_))
+        ));
+        assert!(matches!(FileNameTemplate::new("{id"), Err(PexelsError::InvalidParameter(_))));
+        assert!(matches!(FileNameTemplate::new("/"), Err(PexelsError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let template = FileNameTemplate::new("{collection}/{id}_{quality}.{ext}").unwrap();
+        assert_eq!(template.to_string(), "{collection}/{id}_{quality}.{ext}");
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let mut names = UniqueNames::default();
+        assert_eq!(names.claim(PathBuf::from("a/x.jpg")), PathBuf::from("a/x.jpg"));
+        assert_eq!(names.claim(PathBuf::from("a/x.jpg")), PathBuf::from("a/x_2.jpg"));
+        assert_eq!(names.claim(PathBuf::from("a/x.jpg")), PathBuf::from("a/x_3.jpg"));
+    }
+
+    #[test]
+    fn test_extension_from_url() {
+        assert_eq!(
+            extension_from_url("https://images.pexels.com/photos/1/pexels-photo-1.jpeg?w=940"),
+            Some("jpg".to_string())
+        );
+        assert_eq!(
+            extension_from_url("https://images.pexels.com/photos/1/a.PNG"),
+            Some("png".to_string())
+        );
+        assert_eq!(extension_from_url("https://videos.pexels.com/video-files/1/"), None);
+    }
+}
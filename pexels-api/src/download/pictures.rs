@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use crate::models::Video;
+
+use super::format::MediaKind;
+use super::naming::extension_from_url;
+use super::{BatchReport, DownloadManager, DownloadTarget, ProgressCallback, Result};
+
+/// Quality label of the poster in the manifest
+const POSTER_QUALITY: &str = "poster";
+
+impl DownloadManager {
+    /// Download the poster image of a video
+    ///
+    /// The poster is saved as `poster.<ext>` in the pictures folder of the video,
+    /// `video_<id>_pictures` below `output_dir`, next to the preview frames.
+    ///
+    /// # Arguments
+    /// * `video` - Video whose poster to download
+    /// * `output_dir` - Output directory
+    ///
+    /// # Returns
+    /// The path to download the file
+    pub async fn download_video_poster<P: AsRef<Path>>(
+        &self,
+        video: &Video,
+        output_dir: P,
+    ) -> Result<PathBuf> {
+        let target = picture_target(video, POSTER_QUALITY, "poster", &video.image);
+        self.download_file(&target, pictures_dir(video, output_dir.as_ref()), None).await
+    }
+
+    /// Download every preview frame of a video
+    ///
+    /// The frames are saved in the pictures folder of the video, `video_<id>_pictures` below
+    /// `output_dir`, as `frame_00.<ext>`, `frame_01.<ext>`, ... in the order of their `nr`,
+    /// so that sorting by name gives the playback order.
+    ///
+    /// # Arguments
+    /// * `video` - Video whose preview frames to download
+    /// * `output_dir` - Output directory
+    /// * `progress_callback` - Optional progress callback function
+    ///
+    /// # Returns
+    /// A report of the downloaded frames and the ones that failed
+    pub async fn download_video_pictures<P: AsRef<Path>>(
+        &self,
+        video: &Video,
+        output_dir: P,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<BatchReport> {
+        let mut pictures: Vec<_> = video.video_pictures.iter().collect();
+        pictures.sort_by_key(|picture| picture.nr);
+
+        let last = pictures.last().map_or(0, |picture| picture.nr);
+        let width = last.to_string().len().max(2);
+
+        let items = pictures
+            .into_iter()
+            .map(|picture| {
+                let quality = format!("frame_{}", picture.nr);
+                let name = format!("frame_{:0width$}", picture.nr);
+                let target = picture_target(video, &quality, &name, &picture.picture_url);
+                (MediaKind::Image, video.id.get(), Ok(target))
+            })
+            .collect();
+
+        let dir = pictures_dir(video, output_dir.as_ref());
+        self.run_batch(items, &dir, progress_callback).await
+    }
+}
+
+/// Folder of the poster and preview frames of a video
+///
+/// It doubles as their manifest directory, so pruning photos in `output_dir` never
+/// touches the frames.
+fn pictures_dir(video: &Video, output_dir: &Path) -> PathBuf {
+    output_dir.join(format!("video_{}_pictures", video.id))
+}
+
+/// Build the download target of a poster or preview frame named `name`
+fn picture_target(video: &Video, quality: &str, name: &str, url: &str) -> DownloadTarget {
+    let extension = extension_from_url(url).unwrap_or_else(|| "jpg".to_string());
+    DownloadTarget {
+        media_id: video.id.get(),
+        quality: quality.to_string(),
+        url: url.to_string(),
+        file_name: PathBuf::from(format!("{name}.{extension}")),
+        kind: MediaKind::Image,
+        size: None,
+        attribution: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::VideoFile;
+    use crate::test_support::{manager, response, serve, temp_dir, PNG};
+    use crate::{User, UserId, VideoId, VideoPicture};
+
+    fn mock_video(url: &str) -> Video {
+        Video {
+            id: VideoId::new(7),
+            width: 1920,
+            height: 1080,
+            url: "https://www.pexels.com/video/7/".to_string(),
+            image: url.to_string(),
+            duration: 10,
+            full_res: None,
+            tags: Vec::new(),
+            avg_color: None,
+            user: User {
+                id: UserId::new(1),
+                name: "Jane".to_string(),
+                user_url: String::new(),
+                extra: Default::default(),
+            },
+            video_files: Vec::<VideoFile>::new(),
+            video_pictures: [3, 0, 11]
+                .into_iter()
+                .map(|nr| VideoPicture {
+                    id: u64::from(nr),
+                    nr,
+                    picture_url: url.to_string(),
+                    extra: Default::default(),
+                })
+                .collect(),
+            extra: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_video_pictures() {
+        let url = serve(vec![response("200 OK", PNG); 4]).await;
+        let video = mock_video(&url);
+        let dir = temp_dir("pictures");
+        let manager = manager();
+
+        let report = manager.download_video_pictures(&video, &dir, None).await.unwrap();
+        let poster = manager.download_video_poster(&video, &dir).await.unwrap();
+
+        let frames = dir.join("video_7_pictures");
+        assert!(report.is_complete());
+        assert_eq!(
+            report.paths(),
+            vec![
+                frames.join("frame_00.png"),
+                frames.join("frame_03.png"),
+                frames.join("frame_11.png")
+            ]
+        );
+        assert_eq!(poster, frames.join("poster.png"));
+        assert_eq!(std::fs::read(&poster).unwrap(), PNG);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
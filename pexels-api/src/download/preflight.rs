@@ -0,0 +1,230 @@
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use futures::stream::{self, StreamExt};
+
+use crate::models::{Photo, Video};
+use crate::PexelsError;
+
+use super::naming::existing_file;
+use super::rendition::RenditionSelector;
+use super::{BatchItem, DownloadManager, ImageQuality, Result, VideoQuality};
+
+/// What a batch does when its estimated size exceeds the free disk space or the byte budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Fail the whole batch before downloading anything
+    Refuse,
+    /// Download the items that fit, in order, and report the rest as failed
+    Trim,
+}
+
+/// Estimated download size of a set of media
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeEstimate {
+    /// Sum of the known sizes in bytes
+    pub bytes: u64,
+    /// Number of media whose size is not known, neither from the API nor from a HEAD request
+    pub unknown: usize,
+}
+
+impl DownloadManager {
+    /// Estimate the download size of photos with a HEAD request per photo
+    pub async fn estimate_photos(&self, photos: &[Photo], quality: ImageQuality) -> SizeEstimate {
+        let sizes = stream::iter(photos)
+            .map(|photo| async move { self.remote_size(&self.get_photo_url(photo, quality)).await })
+            .buffered(self.max_concurrent.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        SizeEstimate::from_sizes(sizes)
+    }
+
+    /// Estimate the download size of videos
+    ///
+    /// Uses the `size` of the selected video files, with a HEAD request for files without
+    /// one. Videos without a suitable file count as unknown.
+    pub async fn estimate_videos(&self, videos: &[Video], quality: VideoQuality) -> SizeEstimate {
+        let selector = RenditionSelector::from(quality);
+        let sizes = stream::iter(videos)
+            .map(|video| async {
+                let file = selector.select(&video.video_files).ok()?;
+                match file.size {
+                    Some(size) => Some(size),
+                    None => self.remote_size(&file.link).await,
+                }
+            })
+            .buffered(self.max_concurrent.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        SizeEstimate::from_sizes(sizes)
+    }
+
+    /// Bytes received by this manager and its clones so far
+    pub fn bytes_downloaded(&self) -> u64 {
+        self.bytes_used.load(Ordering::Relaxed)
+    }
+
+    /// Bytes left of the byte budget, `None` without a budget
+    pub(super) fn remaining_budget(&self) -> Option<u64> {
+        self.byte_budget.map(|budget| budget.saturating_sub(self.bytes_downloaded()))
+    }
+
+    /// Count received bytes against the byte budget
+    pub(super) fn consume_budget(&self, bytes: u64) -> Result<()> {
+        let used = self.bytes_used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        match self.byte_budget {
+            Some(budget) if used > budget => Err(PexelsError::BudgetExceeded(budget)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the estimated size of a batch against the free space and the byte budget
+    ///
+    /// Files partly or fully present in `output_dir` only count with their missing bytes.
+    /// Depending on the [`OverflowPolicy`] the whole batch is refused or the items that do
+    /// not fit are turned into failures.
+    pub(super) async fn preflight(&self, items: &mut [BatchItem], output_dir: &Path) -> Result<()> {
+        let Some(policy) = self.space_check else {
+            return Ok(());
+        };
+
+        let needed = stream::iter(items.iter())
+            .map(|(_, _, target)| async move {
+                let Ok(target) = target else {
+                    return 0;
+                };
+                let size = match target.size {
+                    Some(size) => Some(size),
+                    None => self.remote_size(&target.url).await,
+                };
+                let planned = output_dir.join(&target.file_name);
+                let existing = existing_file(&planned, target.kind).await.map_or(0, |(_, len)| len);
+                size.unwrap_or(0).saturating_sub(existing)
+            })
+            .buffered(self.max_concurrent.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        let available = available_space(output_dir)?;
+        let budget = self.remaining_budget();
+        let limit = budget.map_or(available, |budget| budget.min(available));
+        let overflow = |required: u64| match self.byte_budget {
+            Some(total) if budget.is_some_and(|budget| budget < available) => {
+                PexelsError::BudgetExceeded(total)
+            }
+            _ => PexelsError::InsufficientSpace { required, available },
+        };
+
+        let total: u64 = needed.iter().sum();
+        if total <= limit {
+            return Ok(());
+        }
+        if policy == OverflowPolicy::Refuse {
+            return Err(overflow(total));
+        }
+
+        let mut required = 0;
+        for ((_, _, target), size) in items.iter_mut().zip(needed) {
+            if target.is_err() {
+                continue;
+            }
+            if required + size <= limit {
+                required += size;
+            } else {
+                *target = Err(overflow(required + size));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SizeEstimate {
+    fn from_sizes(sizes: Vec<Option<u64>>) -> Self {
+        sizes.into_iter().fold(Self::default(), |mut estimate, size| {
+            match size {
+                Some(size) => estimate.bytes += size,
+                None => estimate.unknown += 1,
+            }
+            estimate
+        })
+    }
+}
+
+/// Free space of the filesystem `dir` is or will be created on
+fn available_space(dir: &Path) -> std::io::Result<u64> {
+    let existing = dir.ancestors().find(|dir| dir.exists()).unwrap_or(Path::new("."));
+    fs4::available_space(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tokio::fs;
+
+    use super::*;
+    use crate::download::format::MediaKind;
+    use crate::download::DownloadTarget;
+    use crate::test_support::{manager, response, serve, temp_dir, PNG};
+
+    fn item(url: &str, id: u64) -> BatchItem {
+        let target = DownloadTarget {
+            media_id: id,
+            quality: "original".to_string(),
+            url: url.to_string(),
+            file_name: PathBuf::from(format!("photo_{id}.png")),
+            kind: MediaKind::Image,
+            size: Some(PNG.len() as u64),
+            attribution: None,
+        };
+        (MediaKind::Image, id, Ok(target))
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_trimmed_to_the_budget() {
+        let dir = temp_dir("budget-trim");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let budget = PNG.len() as u64 * 3 / 2;
+        let manager = manager().with_byte_budget(budget).with_space_check(OverflowPolicy::Trim);
+
+        let report = manager.run_batch(vec![item(&url, 1), item(&url, 2)], &dir, None).await;
+        let report = report.unwrap();
+        assert_eq!(report.paths(), vec![dir.join("photo_1.png")]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].media_id, 2);
+        assert_eq!(report.failed[0].error, PexelsError::BudgetExceeded(budget));
+        assert_eq!(manager.bytes_downloaded(), PNG.len() as u64);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_refused() {
+        let dir = temp_dir("budget-refuse");
+        let manager = manager().with_byte_budget(1).with_space_check(OverflowPolicy::Refuse);
+
+        let result = manager.run_batch(vec![item("http://127.0.0.1:9/", 1)], &dir, None).await;
+        assert_eq!(result.unwrap_err(), PexelsError::BudgetExceeded(1));
+        assert!(!dir.join("photo_1.png").exists());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_a_download() {
+        let dir = temp_dir("budget-stop");
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let manager = manager().with_byte_budget(8);
+
+        let report = manager.run_batch(vec![item(&url, 1)], &dir, None).await.unwrap();
+        assert_eq!(report.failed[0].error, PexelsError::BudgetExceeded(8));
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_size_estimate() {
+        let estimate = SizeEstimate::from_sizes(vec![Some(3), None, Some(4)]);
+        assert_eq!(estimate, SizeEstimate { bytes: 7, unknown: 1 });
+    }
+}
@@ -0,0 +1,398 @@
+use std::cmp::Ordering;
+
+use crate::{models, Orientation, PexelsError, VideoFileQuality};
+
+use super::VideoQuality;
+
+/// Common view of a video file (rendition) so both video models can be selected from
+pub trait Rendition {
+    /// Width in pixels, if known
+    fn width(&self) -> Option<u32>;
+    /// Height in pixels, if known
+    fn height(&self) -> Option<u32>;
+    /// Frames per second, if known
+    fn fps(&self) -> Option<f64>;
+    /// File size in bytes, if known
+    fn size(&self) -> Option<u64>;
+    /// MIME type, e.g. `video/mp4`
+    fn mime_type(&self) -> &str;
+    /// Quality label, e.g. `hd`
+    fn quality(&self) -> Option<&str>;
+    /// Download link
+    fn link(&self) -> &str;
+
+    /// Width and height in pixels, if both are known
+    fn resolution(&self) -> Option<(u32, u32)> {
+        Some((self.width()?, self.height()?))
+    }
+
+    /// Whether the long side has at least 3840 pixels, in either orientation
+    fn is_4k(&self) -> bool {
+        self.resolution().is_some_and(|dims| sides(dims).0 >= 3840)
+    }
+
+    /// Width divided by height, if both are known
+    fn aspect_ratio(&self) -> Option<f64> {
+        let (width, height) = self.resolution()?;
+        (height > 0).then(|| f64::from(width) / f64::from(height))
+    }
+
+    /// Average bitrate in bits per second of a video lasting `duration` seconds, estimated
+    /// from the file size
+    fn bitrate_estimate(&self, duration: u32) -> Option<u64> {
+        let size = self.size()?;
+        (duration > 0).then(|| size * 8 / u64::from(duration))
+    }
+}
+
+impl Rendition for models::VideoFile {
+    fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    fn fps(&self) -> Option<f64> {
+        self.fps
+    }
+
+    fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    fn mime_type(&self) -> &str {
+        self.file_type.as_str()
+    }
+
+    fn quality(&self) -> Option<&str> {
+        self.quality.as_ref().map(VideoFileQuality::as_str)
+    }
+
+    fn link(&self) -> &str {
+        &self.link
+    }
+}
+
+impl Rendition for crate::VideoFile {
+    fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    fn fps(&self) -> Option<f64> {
+        self.fps
+    }
+
+    fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    fn mime_type(&self) -> &str {
+        self.file_type.as_str()
+    }
+
+    fn quality(&self) -> Option<&str> {
+        self.quality.as_ref().map(VideoFileQuality::as_str)
+    }
+
+    fn link(&self) -> &str {
+        &self.file_link
+    }
+}
+
+/// Picks the best rendition from a list of video files
+///
+/// Every constraint a file does not meet counts as a violation. The files with the fewest
+/// violations are ranked by closeness to the target resolution (or the highest resolution
+/// without a target), then by frame rate if preferred, then by smaller size and finally by
+/// list order, so the result is always deterministic. In strict mode a file with any
+/// violation is never returned.
+///
+/// Resolutions are compared independently of orientation: `max_resolution(1920, 1080)`
+/// also admits a 1080x1920 portrait file.
+///
+/// # Example
+/// ```rust
+/// use pexels_api::RenditionSelector;
+///
+/// let selector = RenditionSelector::new()
+///     .mime_type("video/mp4")
+///     .max_resolution(1920, 1080)
+///     .prefer_highest_fps(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenditionSelector {
+    quality: Option<String>,
+    target_resolution: Option<(u32, u32)>,
+    max_resolution: Option<(u32, u32)>,
+    prefer_highest_fps: bool,
+    max_file_size: Option<u64>,
+    orientation: Option<Orientation>,
+    mime_type: Option<String>,
+    strict: bool,
+}
+
+impl RenditionSelector {
+    /// Creates a new [`RenditionSelector`] that prefers the highest resolution
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accept files with this quality label (case-insensitive), e.g. `hd`
+    pub fn quality(mut self, quality: impl Into<String>) -> Self {
+        self.quality = Some(quality.into());
+        self
+    }
+
+    /// Prefer the file whose resolution is closest to `width` x `height`
+    pub fn target_resolution(mut self, width: u32, height: u32) -> Self {
+        self.target_resolution = Some((width, height));
+        self
+    }
+
+    /// Only accept files no larger than `width` x `height`
+    pub fn max_resolution(mut self, width: u32, height: u32) -> Self {
+        self.max_resolution = Some((width, height));
+        self
+    }
+
+    /// Among equally good resolutions, prefer the highest frame rate
+    pub fn prefer_highest_fps(mut self, prefer: bool) -> Self {
+        self.prefer_highest_fps = prefer;
+        self
+    }
+
+    /// Only accept files of at most `bytes`
+    pub fn max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Only accept files with this orientation
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// Only accept files with this MIME type (case-insensitive), e.g. `video/mp4`
+    pub fn mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Fail instead of falling back to a file that violates a constraint
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Select the best rendition
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::NoSuitableRendition`] if `files` is empty, or in strict mode
+    /// if no file meets every constraint.
+    pub fn select<'a, R: Rendition>(&self, files: &'a [R]) -> Result<&'a R, PexelsError> {
+        let best = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (self.violations(file), index, file))
+            .min_by(|a, b| a.0.cmp(&b.0).then_with(|| self.rank(a.2, b.2)).then(a.1.cmp(&b.1)));
+
+        match best {
+            Some((violations, _, file)) if violations == 0 || !self.strict => Ok(file),
+            Some(_) => Err(PexelsError::NoSuitableRendition(format!(
+                "none of {} files match {self:?}",
+                files.len()
+            ))),
+            None => Err(PexelsError::NoSuitableRendition("no video files".to_string())),
+        }
+    }
+
+    /// Number of constraints the file does not meet, unknown values never violate
+    fn violations<R: Rendition>(&self, file: &R) -> usize {
+        let mut violations = 0;
+
+        if let Some(quality) = &self.quality {
+            if !file.quality().is_some_and(|q| q.eq_ignore_ascii_case(quality)) {
+                violations += 1;
+            }
+        }
+
+        if let Some(mime_type) = &self.mime_type {
+            if !file.mime_type().eq_ignore_ascii_case(mime_type) {
+                violations += 1;
+            }
+        }
+
+        if let (Some((max_w, max_h)), Some(dims)) = (self.max_resolution, file.resolution()) {
+            let (long, short) = sides(dims);
+            let (max_long, max_short) = sides((max_w, max_h));
+            if long > max_long || short > max_short {
+                violations += 1;
+            }
+        }
+
+        if let (Some(max_size), Some(size)) = (self.max_file_size, file.size()) {
+            if size > max_size {
+                violations += 1;
+            }
+        }
+
+        if let (Some(orientation), Some((width, height))) = (&self.orientation, file.resolution()) {
+            let actual = match width.cmp(&height) {
+                Ordering::Greater => Orientation::Landscape,
+                Ordering::Less => Orientation::Portrait,
+                Ordering::Equal => Orientation::Square,
+            };
+            if &actual != orientation {
+                violations += 1;
+            }
+        }
+
+        violations
+    }
+
+    /// Order two files, `Less` means `a` is preferred
+    fn rank<R: Rendition>(&self, a: &R, b: &R) -> Ordering {
+        let area = |file: &R| file.resolution().map_or(0, |(w, h)| u64::from(w) * u64::from(h));
+
+        let resolution = match self.target_resolution {
+            Some((width, height)) => {
+                let target = u64::from(width) * u64::from(height);
+                area(a).abs_diff(target).cmp(&area(b).abs_diff(target))
+            }
+            None => area(b).cmp(&area(a)),
+        };
+
+        let fps = if self.prefer_highest_fps {
+            let fps = |file: &R| file.fps().unwrap_or(0.0);
+            fps(b).total_cmp(&fps(a))
+        } else {
+            Ordering::Equal
+        };
+
+        let size = a.size().unwrap_or(u64::MAX).cmp(&b.size().unwrap_or(u64::MAX));
+
+        resolution.then(fps).then(size)
+    }
+}
+
+impl From<VideoQuality> for RenditionSelector {
+    fn from(quality: VideoQuality) -> Self {
+        match quality {
+            VideoQuality::HD => RenditionSelector::new().quality("hd"),
+            VideoQuality::SD => RenditionSelector::new().quality("sd"),
+            VideoQuality::Tiny => {
+                RenditionSelector::new().mime_type("video/mp4").max_resolution(640, 360)
+            }
+        }
+    }
+}
+
+/// (long side, short side)
+fn sides((width, height): (u32, u32)) -> (u32, u32) {
+    (width.max(height), width.min(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaMime;
+
+    fn file(
+        id: u64,
+        quality: &str,
+        width: u32,
+        height: u32,
+        fps: f64,
+        size: u64,
+    ) -> models::VideoFile {
+        models::VideoFile {
+            id,
+            quality: Some(quality.into()),
+            file_type: MediaMime::Mp4,
+            width: Some(width),
+            height: Some(height),
+            fps: Some(fps),
+            size: Some(size),
+            link: format!("https://videos.pexels.com/video-files/{id}.mp4"),
+            extra: Default::default(),
+        }
+    }
+
+    fn files() -> Vec<models::VideoFile> {
+        vec![
+            file(1, "sd", 640, 360, 25.0, 1_000),
+            file(2, "hd", 1280, 720, 25.0, 4_000),
+            file(3, "hd", 1920, 1080, 25.0, 9_000),
+            file(4, "hd", 1920, 1080, 50.0, 12_000),
+            file(5, "uhd", 3840, 2160, 25.0, 30_000),
+        ]
+    }
+
+    #[test]
+    fn test_highest_resolution_by_default() {
+        let files = files();
+        assert_eq!(RenditionSelector::new().select(&files).unwrap().id, 5);
+    }
+
+    #[test]
+    fn test_max_resolution_and_fps() {
+        let files = files();
+        let selector = RenditionSelector::new().max_resolution(1920, 1080);
+        assert_eq!(selector.select(&files).unwrap().id, 3);
+        assert_eq!(selector.prefer_highest_fps(true).select(&files).unwrap().id, 4);
+    }
+
+    #[test]
+    fn test_target_resolution_and_size() {
+        let files = files();
+        let selector = RenditionSelector::new().target_resolution(1280, 720);
+        assert_eq!(selector.select(&files).unwrap().id, 2);
+
+        let selector = RenditionSelector::new().max_file_size(5_000);
+        assert_eq!(selector.select(&files).unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_tiny_requires_both_sides_small() {
+        let mut files = files();
+        files.push(file(6, "sd", 960, 360, 25.0, 2_000));
+        let selector = RenditionSelector::from(VideoQuality::Tiny);
+        assert_eq!(selector.select(&files).unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_fallback_and_strict() {
+        let files = files();
+        let selector = RenditionSelector::new().orientation(Orientation::Portrait);
+        assert_eq!(selector.select(&files).unwrap().id, 5);
+        assert!(matches!(
+            selector.strict(true).select(&files),
+            Err(PexelsError::NoSuitableRendition(_))
+        ));
+
+        let empty: Vec<models::VideoFile> = Vec::new();
+        assert!(matches!(
+            RenditionSelector::new().select(&empty),
+            Err(PexelsError::NoSuitableRendition(_))
+        ));
+    }
+
+    #[test]
+    fn test_derived_helpers() {
+        let files = files();
+        assert_eq!(files[1].resolution(), Some((1280, 720)));
+        assert!(files[4].is_4k());
+        assert!(!files[3].is_4k());
+        assert_eq!(files[2].aspect_ratio(), Some(16.0 / 9.0));
+        assert_eq!(files[2].bitrate_estimate(10), Some(7_200));
+        assert_eq!(files[2].bitrate_estimate(0), None);
+        assert_eq!(files[4].quality, Some(VideoFileQuality::Uhd));
+    }
+}
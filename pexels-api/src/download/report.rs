@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use crate::PexelsError;
+
+use super::checksum::Checksum;
+use super::format::MediaKind;
+
+/// Outcome of a batch download
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// Files that are in place, downloaded now or kept from an earlier run
+    pub downloaded: Vec<DownloadedFile>,
+    /// Media that could not be downloaded
+    pub failed: Vec<DownloadFailure>,
+}
+
+/// A file of a batch that is in place
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadedFile {
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Digest of the file, present when checksums are enabled
+    pub checksum: Option<Checksum>,
+    /// Why the attribution could not be embedded into the file, which is kept without it
+    pub attribution_error: Option<String>,
+}
+
+/// A photo or video that could not be downloaded
+#[derive(Debug)]
+pub struct DownloadFailure {
+    pub kind: MediaKind,
+    /// Pexels id of the photo or video
+    pub media_id: u64,
+    pub error: PexelsError,
+}
+
+impl BatchReport {
+    /// Whether every item was downloaded
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Paths of the files that are in place
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.downloaded.iter().map(|file| file.path.clone()).collect()
+    }
+
+    /// Print the failures and keep the downloaded paths, for the slice based batch methods
+    pub(crate) fn log_failures(self) -> Vec<PathBuf> {
+        for failure in &self.failed {
+            eprintln!("Download error: {}", failure.error);
+        }
+        self.paths()
+    }
+}
@@ -0,0 +1,351 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use reqwest::{header, Response, StatusCode};
+use tokio::sync::OwnedSemaphorePermit;
+
+use crate::PexelsError;
+
+use super::format::{detect_format, MediaFormat, MediaKind, SNIFF_LEN};
+use super::throttle::BandwidthLimiter;
+use super::{DownloadManager, ProgressCallback, Result};
+
+/// Delay before the first retry, doubled for every further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for the delay between two attempts
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// A throttled byte stream of a single download
+///
+/// Interrupted transfers are transparently resumed with a `Range` request, up to the
+/// manager's retry limit. Once an error is yielded the stream ends.
+pub struct MediaStream {
+    format: Option<MediaFormat>,
+    offset: u64,
+    total_size: Option<u64>,
+    inner: BoxStream<'static, Result<Bytes>>,
+}
+
+impl MediaStream {
+    /// The detected format of the media, if it could be determined
+    pub fn format(&self) -> Option<MediaFormat> {
+        self.format
+    }
+
+    /// The position in the resource the stream starts at, non-zero when resuming
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The size of the whole resource in bytes, if the server reported it
+    pub fn total_size(&self) -> Option<u64> {
+        self.total_size
+    }
+}
+
+impl Stream for MediaStream {
+    type Item = Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.poll_next_unpin(cx)
+    }
+}
+
+/// State of a running transfer
+struct Transfer {
+    manager: DownloadManager,
+    url: String,
+    per_download: BandwidthLimiter,
+    /// Bytes read while detecting the format, not yet handed out
+    head: Option<Bytes>,
+    body: BoxStream<'static, reqwest::Result<Bytes>>,
+    /// Position in the resource of the next byte of `body`
+    position: u64,
+    /// Position in the resource up to which bytes have been handed out
+    delivered: u64,
+    total_size: u64,
+    retries_left: u32,
+    progress_callback: Option<ProgressCallback>,
+    /// Slot of the host's concurrency limit, held until the transfer ends
+    _host_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Transfer {
+    async fn next_chunk(&mut self) -> Result<Option<Bytes>> {
+        loop {
+            let chunk = match self.head.take() {
+                Some(head) => head,
+                None => match self.body.next().await {
+                    Some(Ok(chunk)) => chunk,
+                    Some(Err(e)) if self.retries_left > 0 && is_retryable_error(&e) => {
+                        self.retries_left -= 1;
+                        tokio::time::sleep(retry_delay(
+                            self.manager.max_retries - self.retries_left,
+                        ))
+                        .await;
+                        self.reconnect().await?;
+                        continue;
+                    }
+                    Some(Err(e)) => return Err(e.into()),
+                    None => return Ok(None),
+                },
+            };
+
+            // Drop whatever was already handed out, e.g. when the server ignored `Range`
+            let chunk_start = self.position;
+            self.position += chunk.len() as u64;
+            if self.position <= self.delivered {
+                continue;
+            }
+            let chunk = chunk.slice(self.delivered.saturating_sub(chunk_start) as usize..);
+            self.delivered = self.position;
+
+            let len = chunk.len() as u64;
+            self.per_download.acquire(len).await;
+            self.manager.bandwidth.acquire(len).await;
+
+            // Call progress callback (if provided)
+            if let Some(cb) = self.progress_callback {
+                cb(self.delivered, self.total_size);
+            }
+
+            return Ok(Some(chunk));
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        let response =
+            self.manager.send_with_retry(&self.url, self.delivered, &mut self.retries_left).await?;
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            self.position = self.delivered;
+            self.body = futures::stream::empty().boxed();
+            return Ok(());
+        }
+        self.position =
+            if response.status() == StatusCode::PARTIAL_CONTENT { self.delivered } else { 0 };
+        self.body = response.bytes_stream().boxed();
+        Ok(())
+    }
+}
+
+impl DownloadManager {
+    /// Open a stream of the resource starting at byte `start`
+    ///
+    /// The format is detected and checked against `expected` before the stream is returned.
+    pub(super) async fn open_stream_at(
+        &self,
+        url: &str,
+        start: u64,
+        expected: MediaKind,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<MediaStream> {
+        let host_permit = self.host_limits.acquire(url).await;
+        let mut retries_left = self.max_retries;
+        let response = self.send_with_retry(url, start, &mut retries_left).await?;
+
+        // Nothing is left past `start` when the file on disk is already complete
+        let complete = response.status() == StatusCode::RANGE_NOT_SATISFIABLE;
+        let resumed = complete || response.status() == StatusCode::PARTIAL_CONTENT;
+        let position = if resumed { start } else { 0 };
+        let total_size = if complete {
+            Some(start)
+        } else {
+            response.content_length().map(|len| len + position)
+        };
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .filter(|_| !complete)
+            .map(str::to_string);
+
+        let mut body = if complete {
+            futures::stream::empty().boxed()
+        } else {
+            response.bytes_stream().boxed()
+        };
+
+        // Buffer the first bytes so the real format is known before anything is handed out.
+        // A resumed download continues mid-file, so only the headers can be checked.
+        let mut head = Vec::new();
+        if position == 0 {
+            while head.len() < SNIFF_LEN {
+                match body.next().await {
+                    Some(chunk) => head.extend_from_slice(&chunk?),
+                    None => break,
+                }
+            }
+        }
+
+        let format = detect_format(expected, content_type.as_deref(), url, &head)?;
+
+        let transfer = Transfer {
+            manager: self.clone(),
+            url: url.to_string(),
+            // Each transfer gets its own bucket that follows the per-download limit
+            per_download: self.per_download.fork(),
+            head: (!head.is_empty()).then(|| Bytes::from(head)),
+            body,
+            position,
+            delivered: start,
+            total_size: total_size.unwrap_or(0),
+            retries_left,
+            progress_callback,
+            _host_permit: host_permit,
+        };
+
+        let inner = futures::stream::unfold(Some(transfer), |transfer| async move {
+            let mut transfer = transfer?;
+            match transfer.next_chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(transfer))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+        .boxed();
+
+        Ok(MediaStream { format, offset: start, total_size, inner })
+    }
+
+    /// Send a GET request for the resource from byte `start`, retrying transient failures
+    pub(super) async fn send_with_retry(
+        &self,
+        url: &str,
+        start: u64,
+        retries_left: &mut u32,
+    ) -> Result<Response> {
+        loop {
+            let mut request = self.client.get(url);
+            if start > 0 {
+                request = request.header(header::RANGE, format!("bytes={start}-"));
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                // A range starting at the end of the resource means it is already complete
+                Ok(response)
+                    if start > 0
+                        && response.status() == StatusCode::RANGE_NOT_SATISFIABLE
+                        && content_range_total(&response) == Some(start) =>
+                {
+                    return Ok(response)
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let error =
+                        PexelsError::DownloadError(format!("Failed to download file: {status}"));
+                    if !is_retryable_status(status) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) if is_retryable_error(&e) => PexelsError::from(e),
+                Err(e) => return Err(e.into()),
+            };
+
+            if *retries_left == 0 {
+                return Err(error);
+            }
+            *retries_left -= 1;
+            tokio::time::sleep(retry_delay(self.max_retries - *retries_left)).await;
+        }
+    }
+
+    /// The size the server announces for the resource, from a `HEAD` request
+    ///
+    /// Returns `None` if the request fails, the server doesn't answer `HEAD` successfully or
+    /// omits the size.
+    pub(super) async fn remote_size(&self, url: &str) -> Option<u64> {
+        let _host_permit = self.host_limits.acquire(url).await;
+        let response = self.client.head(url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        // `Response::content_length` is the size of the empty body of a `HEAD` response
+        response.headers().get(header::CONTENT_LENGTH)?.to_str().ok()?.parse().ok()
+    }
+}
+
+/// The total size in a `Content-Range` header, e.g. `1234` in `bytes */1234`
+fn content_range_total(response: &Response) -> Option<u64> {
+    let value = response.headers().get(header::CONTENT_RANGE)?.to_str().ok()?;
+    value.rsplit('/').next()?.trim().parse().ok()
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_body() || error.is_decode()
+}
+
+/// Exponential backoff for the given (1-based) attempt
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(RETRY_MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{manager, response, serve, PNG};
+
+    async fn collect(mut stream: MediaStream) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_stream_detects_format() {
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let stream = manager().open_stream(&url, MediaKind::Image).await.unwrap();
+        assert_eq!(stream.format(), Some(MediaFormat::Png));
+        assert_eq!(stream.total_size(), Some(PNG.len() as u64));
+        assert_eq!(collect(stream).await, PNG);
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_bytes_when_range_is_ignored() {
+        let url = serve(vec![response("200 OK", PNG)]).await;
+        let stream = manager().open_stream_at(&url, 10, MediaKind::Image, None).await.unwrap();
+        assert_eq!(stream.offset(), 10);
+        assert_eq!(collect(stream).await, &PNG[10..]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let url =
+            serve(vec![response("503 Service Unavailable", b""), response("200 OK", PNG)]).await;
+        let stream = manager().open_stream(&url, MediaKind::Image).await.unwrap();
+        assert_eq!(collect(stream).await, PNG);
+
+        let url = serve(vec![response("503 Service Unavailable", b"")]).await;
+        let result = manager().with_max_retries(0).open_stream(&url, MediaKind::Image).await;
+        assert!(matches!(result, Err(PexelsError::DownloadError(_))));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_millis(500));
+        assert_eq!(retry_delay(3), Duration::from_secs(2));
+        assert_eq!(retry_delay(30), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Token bucket used to cap download bandwidth in bytes per second
+///
+/// A limiter can be shared by any number of concurrent transfers and its limit can be
+/// changed at runtime. A limit of `None` disables throttling.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    /// Bytes per second, `0` means unlimited. Shared with limiters created by `fork`.
+    rate: Arc<AtomicU64>,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available bytes, negative while transfers are in debt
+    available: f64,
+    last_refill: Instant,
+}
+
+impl BandwidthLimiter {
+    /// Create a new limiter
+    ///
+    /// # Arguments
+    /// * `bytes_per_second` - Maximum throughput, `None` for unlimited
+    pub fn new(bytes_per_second: Option<u64>) -> Self {
+        Self::with_rate(Arc::new(AtomicU64::new(bytes_per_second.unwrap_or(0))))
+    }
+
+    /// Create a limiter that never throttles
+    pub fn unlimited() -> Self {
+        Self::new(None)
+    }
+
+    /// The current limit in bytes per second, `None` if unlimited
+    pub fn limit(&self) -> Option<u64> {
+        match self.rate.load(Ordering::Relaxed) {
+            0 => None,
+            rate => Some(rate),
+        }
+    }
+
+    /// Change the limit, takes effect for all transfers using this limiter
+    pub fn set_limit(&self, bytes_per_second: Option<u64>) {
+        self.rate.store(bytes_per_second.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Wait until `bytes` may be transferred without exceeding the limit
+    pub async fn acquire(&self, bytes: u64) {
+        let rate = match self.limit() {
+            Some(rate) => rate as f64,
+            None => return,
+        };
+
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            // Allow at most one second worth of burst
+            bucket.available = (bucket.available + elapsed * rate).min(rate);
+            bucket.last_refill = now;
+            bucket.available -= bytes as f64;
+
+            if bucket.available < 0.0 {
+                Duration::from_secs_f64(-bucket.available / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Create an independent bucket that follows the same (runtime adjustable) limit
+    pub(crate) fn fork(&self) -> Self {
+        Self::with_rate(Arc::clone(&self.rate))
+    }
+
+    fn with_rate(rate: Arc<AtomicU64>) -> Self {
+        Self { rate, bucket: Mutex::new(Bucket { available: 0.0, last_refill: Instant::now() }) }
+    }
+}
+
+impl Default for BandwidthLimiter {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unlimited_does_not_wait() {
+        let limiter = BandwidthLimiter::unlimited();
+        let start = Instant::now();
+        limiter.acquire(u64::MAX).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_limit_is_enforced() {
+        let limiter = BandwidthLimiter::new(Some(10_000));
+        let start = Instant::now();
+        limiter.acquire(1_000).await;
+        limiter.acquire(1_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(180));
+    }
+
+    #[test]
+    fn test_fork_shares_limit() {
+        let limiter = BandwidthLimiter::new(Some(1_024));
+        let forked = limiter.fork();
+        limiter.set_limit(Some(2_048));
+        assert_eq!(forked.limit(), Some(2_048));
+        forked.set_limit(None);
+        assert_eq!(limiter.limit(), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Typed ids of Pexels resources
+//!
+//! Each kind of resource has its own id type, so a video id can't be passed where a photo
+//! id is expected. They serialize as the bare number or string the API uses.
+
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! numeric_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(u64);
+
+        impl $name {
+            /// Wrap a raw id
+            pub const fn new(id: u64) -> Self {
+                Self(id)
+            }
+
+            /// The raw id
+            pub const fn get(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.trim().parse().map(Self)
+            }
+        }
+    };
+}
+
+numeric_id!(
+    /// Id of a photo
+    PhotoId
+);
+
+numeric_id!(
+    /// Id of a video
+    VideoId
+);
+
+numeric_id!(
+    /// Id of a user, e.g. a photographer or videographer
+    UserId
+);
+
+/// Id of a collection, e.g. `tszhfva`
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CollectionId(String);
+
+impl CollectionId {
+    /// Wrap a raw id
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The raw id
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for CollectionId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for CollectionId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<&CollectionId> for CollectionId {
+    fn from(id: &CollectionId) -> Self {
+        id.clone()
+    }
+}
+
+impl AsRef<str> for CollectionId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CollectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for CollectionId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_serialize_transparently() {
+        let id: PhotoId = serde_json::from_str("4294967296").unwrap();
+        assert_eq!(id.get(), u64::from(u32::MAX) + 1);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "4294967296");
+
+        let id: CollectionId = serde_json::from_str("\"tszhfva\"").unwrap();
+        assert_eq!(id.as_str(), "tszhfva");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"tszhfva\"");
+    }
+
+    #[test]
+    fn test_ids_parse_and_display() {
+        assert_eq!("123".parse::<VideoId>().unwrap(), VideoId::new(123));
+        assert!("abc".parse::<UserId>().is_err());
+        assert_eq!(UserId::from(7).to_string(), "7");
+        assert_eq!(CollectionId::from("abc").to_string(), "abc");
+    }
+}
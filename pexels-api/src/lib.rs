@@ -64,11 +64,20 @@ If you want to get a random photo, you can use the `curated_photo` function and
 
 mod client;
 mod collections;
+mod color;
 mod domain;
 mod download;
+mod ids;
+mod locale;
 mod models;
+mod page_url;
 mod photos;
+mod schema;
 mod search;
+#[cfg(test)]
+mod test_support;
+mod validate;
+mod video_file;
 mod videos;
 
 /// collections module
@@ -108,16 +117,35 @@ pub use videos::video::FetchVideo;
 pub use videos::video::FetchVideoBuilder;
 
 pub use client::PexelsClient;
+pub use color::{Hsl, Lab, RgbColor};
+pub use ids::{CollectionId, PhotoId, UserId, VideoId};
+pub use locale::Locale;
+pub use page_url::Paginated;
+pub use schema::UnknownFields;
 pub use search::{
     CollectionMediaParams, PaginationParams, PopularVideoParams, SearchParams, VideoSearchParams,
 };
+pub use video_file::{MediaMime, VideoFileQuality};
 
+pub use download::BandwidthLimiter;
 pub use download::DownloadManager;
+pub use download::DownloadManagerBuilder;
+pub use download::ExistingFilePolicy;
 pub use download::ProgressCallback;
+pub use download::MANIFEST_FILE_NAME;
+pub use download::{embed_attribution, embed_attribution_bytes, Attribution, SidecarFormat};
+pub use download::{BatchReport, CollectionDownloadOptions, DownloadFailure, DownloadedFile};
+pub use download::{Checksum, ChecksumAlgorithm, Manifest, ManifestEntry, VerifyReport};
+pub use download::{FileNameTemplate, NameContext, DEFAULT_PHOTO_TEMPLATE, DEFAULT_VIDEO_TEMPLATE};
+pub use download::{Fit, ImageRequest, Rendition, RenditionSelector};
+pub use download::{ImageQuality, MediaFormat, MediaKind, MediaStream, VideoQuality};
+pub use download::{OverflowPolicy, SizeEstimate};
 
 /// import crate
 use reqwest::Client;
 use reqwest::Error as ReqwestError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Error as JSONError;
 use serde_json::Value;
 use std::env::VarError;
@@ -138,6 +166,61 @@ const PEXELS_COLLECTIONS_PATH: &str = "collections";
 /// Pexels API URL
 const PEXELS_API: &str = "https://api.pexels.com";
 
+/// Implements `ALL`, `as_str`, `Display`, `FromStr` and serde for a query enum from the
+/// query value of each variant, plus aliases accepted when parsing. Parsing ignores case.
+/// Variants in the optional `unlisted` block are parsed and printed but left out of `ALL`.
+macro_rules! query_enum {
+    (
+        $name:ident,
+        $error:expr,
+        { $($variant:ident => $value:literal $(| $alias:literal)*,)* }
+        $(, unlisted { $($unlisted:ident => $unlisted_value:literal,)* })?
+    ) => {
+        impl $name {
+            /// Every variant, e.g. to list the accepted values in a CLI
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            /// The value sent in queries
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $($($name::$unlisted => $unlisted_value,)*)?
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = PexelsError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.trim().to_lowercase().as_str() {
+                    $($value $(| $alias)* => Ok($name::$variant),)*
+                    $($($unlisted_value => Ok($name::$unlisted),)*)?
+                    _ => Err($error),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 /// Desired photo orientation.
 /// Supported values: `landscape`, `portrait`, `square`.
 /// Default: `landscape`.
@@ -150,46 +233,18 @@ const PEXELS_API: &str = "https://api.pexels.com";
 /// let orientation = Orientation::from_str("landscape").unwrap();
 /// assert_eq!(orientation, Orientation::Landscape);
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum Orientation {
     Landscape,
     Portrait,
     Square,
 }
 
-impl Orientation {
-    fn as_str(&self) -> &str {
-        match self {
-            Orientation::Landscape => "landscape",
-            Orientation::Portrait => "portrait",
-            Orientation::Square => "square",
-        }
-    }
-}
-
-impl FromStr for Orientation {
-    type Err = PexelsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "landscape" => Ok(Orientation::Landscape),
-            "portrait" => Ok(Orientation::Portrait),
-            "square" => Ok(Orientation::Square),
-            _ => Err(PexelsError::ParseMediaSortError),
-        }
-    }
-}
-
-impl Display for Orientation {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            Orientation::Landscape => "landscape".to_string(),
-            Orientation::Portrait => "portrait".to_string(),
-            Orientation::Square => "square".to_string(),
-        };
-        write!(f, "{str}")
-    }
-}
+query_enum!(Orientation, PexelsError::ParseOrientationError, {
+    Landscape => "landscape",
+    Portrait => "portrait",
+    Square => "square",
+});
 
 /// Specifies the order of items in the media collection.
 /// Supported values: `asc`, `desc`. Default: `asc`.
@@ -202,32 +257,16 @@ impl Display for Orientation {
 /// let sort = MediaSort::from_str("asc").unwrap();
 /// assert_eq!(sort, MediaSort::Asc);
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum MediaSort {
     Asc,
     Desc,
 }
 
-impl MediaSort {
-    pub(crate) fn as_str(&self) -> &str {
-        match self {
-            MediaSort::Asc => "asc",
-            MediaSort::Desc => "desc",
-        }
-    }
-}
-
-impl FromStr for MediaSort {
-    type Err = PexelsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "asc" => Ok(MediaSort::Asc),
-            "desc" => Ok(MediaSort::Desc),
-            _ => Err(PexelsError::ParseMediaSortError),
-        }
-    }
-}
+query_enum!(MediaSort, PexelsError::ParseMediaSortError, {
+    Asc => "asc",
+    Desc => "desc",
+});
 
 /// Specifies the type of media to request.
 /// If not provided or invalid, all media types will be returned.
@@ -244,153 +283,19 @@ impl FromStr for MediaSort {
 ///     Err(e) => eprintln!("Error parsing media type: {:?}", e),
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum MediaType {
     Photo,
     Video,
     Empty,
 }
 
-impl MediaType {
-    pub(crate) fn as_str(&self) -> &str {
-        match self {
-            MediaType::Photo => "photos",
-            MediaType::Video => "videos",
-            MediaType::Empty => "",
-        }
-    }
-}
-
-impl FromStr for MediaType {
-    type Err = PexelsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "photo" | "photos" => Ok(MediaType::Photo),
-            "video" | "videos" => Ok(MediaType::Video),
-            "" => Ok(MediaType::Empty),
-            _ => Err(PexelsError::ParseMediaTypeError),
-        }
-    }
-}
-
-/// Specifies the locale for the search query.
-/// Supported values: `en-US`, `pt-BR`, `es-ES`, `ca-ES`, `de-DE`, `it-IT`, `fr-FR`, `sv-SE`, `id-ID`, `pl-PL`, `ja-JP`, `zh-TW`, `zh-CN`, `ko-KR`, `th-TH`, `nl-NL`, `hu-HU`, `vi-VN`, `cs-CZ`, `da-DK`, `fi-FI`, `uk-UA`, `el-GR`, `ro-RO`, `nb-NO`, `sk-SK`, `tr-TR`, `ru-RU`.
-/// Default: `en-US`.
-///
-/// # Example
-/// ```rust
-/// use pexels_api::Locale;
-/// use std::str::FromStr;
-///
-/// let locale = Locale::from_str("en-US").unwrap();
-/// assert_eq!(locale, Locale::en_US);
-/// ```
-#[allow(non_camel_case_types)]
-#[derive(PartialEq, Debug)]
-pub enum Locale {
-    en_US,
-    pt_BR,
-    es_ES,
-    ca_ES,
-    de_DE,
-    it_IT,
-    fr_FR,
-    sv_SE,
-    id_ID,
-    pl_PL,
-    ja_JP,
-    zh_TW,
-    zh_CN,
-    ko_KR,
-    th_TH,
-    nl_NL,
-    hu_HU,
-    vi_VN,
-    cs_CZ,
-    da_DK,
-    fi_FI,
-    uk_UA,
-    el_GR,
-    ro_RO,
-    nb_NO,
-    sk_SK,
-    tr_TR,
-    ru_RU,
-}
-
-impl Locale {
-    fn as_str(&self) -> &str {
-        match self {
-            Locale::en_US => "en-US",
-            Locale::pt_BR => "pt-BR",
-            Locale::es_ES => "es-ES",
-            Locale::ca_ES => "ca-ES",
-            Locale::de_DE => "de-DE",
-            Locale::it_IT => "it-IT",
-            Locale::fr_FR => "fr-FR",
-            Locale::sv_SE => "sv-SE",
-            Locale::id_ID => "id-ID",
-            Locale::pl_PL => "pl-PL",
-            Locale::ja_JP => "ja-JP",
-            Locale::zh_TW => "zh-TW",
-            Locale::zh_CN => "zh-CN",
-            Locale::ko_KR => "ko-KR",
-            Locale::th_TH => "th-TH",
-            Locale::nl_NL => "nl-NL",
-            Locale::hu_HU => "hu-HU",
-            Locale::vi_VN => "vi-VN",
-            Locale::cs_CZ => "cs-CZ",
-            Locale::da_DK => "da-DK",
-            Locale::fi_FI => "fi-FI",
-            Locale::uk_UA => "uk-UA",
-            Locale::el_GR => "el-GR",
-            Locale::ro_RO => "ro-RO",
-            Locale::nb_NO => "nb-NO",
-            Locale::sk_SK => "sk-SK",
-            Locale::tr_TR => "tr-TR",
-            Locale::ru_RU => "-ES",
-        }
-    }
-}
-
-impl FromStr for Locale {
-    type Err = PexelsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "en-us" => Ok(Locale::en_US),
-            "pt-br" => Ok(Locale::pt_BR),
-            "es-es" => Ok(Locale::es_ES),
-            "ca-es" => Ok(Locale::ca_ES),
-            "de-de" => Ok(Locale::de_DE),
-            "it-it" => Ok(Locale::it_IT),
-            "fr-fr" => Ok(Locale::fr_FR),
-            "sv-se" => Ok(Locale::sv_SE),
-            "id-id" => Ok(Locale::id_ID),
-            "pl-pl" => Ok(Locale::pl_PL),
-            "ja-jp" => Ok(Locale::ja_JP),
-            "zh-tw" => Ok(Locale::zh_TW),
-            "zh-cn" => Ok(Locale::zh_CN),
-            "ko-kr" => Ok(Locale::ko_KR),
-            "th-th" => Ok(Locale::th_TH),
-            "nl-nl" => Ok(Locale::nl_NL),
-            "hu-hu" => Ok(Locale::hu_HU),
-            "vi-vn" => Ok(Locale::vi_VN),
-            "cs-cz" => Ok(Locale::cs_CZ),
-            "da-dk" => Ok(Locale::da_DK),
-            "fi-fi" => Ok(Locale::fi_FI),
-            "uk-ua" => Ok(Locale::uk_UA),
-            "el-gr" => Ok(Locale::el_GR),
-            "ro-ro" => Ok(Locale::ro_RO),
-            "nb-no" => Ok(Locale::nb_NO),
-            "sk-sk" => Ok(Locale::sk_SK),
-            "tr-tr" => Ok(Locale::tr_TR),
-            "ru-ru" => Ok(Locale::ru_RU),
-            _ => Err(PexelsError::ParseLocaleError),
-        }
-    }
-}
+query_enum!(MediaType, PexelsError::ParseMediaTypeError, {
+    Photo => "photos" | "photo",
+    Video => "videos" | "video",
+}, unlisted {
+    Empty => "",
+});
 
 /// Specifies the minimum size for videos or photos.
 /// Supported values: `large`, `medium`, `small`.
@@ -403,46 +308,18 @@ impl FromStr for Locale {
 /// let size = Size::from_str("large").unwrap();
 /// assert_eq!(size, Size::Large);
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum Size {
     Large,
     Medium,
     Small,
 }
 
-impl Size {
-    fn as_str(&self) -> &str {
-        match self {
-            Size::Large => "large",
-            Size::Medium => "medium",
-            Size::Small => "small",
-        }
-    }
-}
-
-impl FromStr for Size {
-    type Err = PexelsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "large" => Ok(Size::Large),
-            "medium" => Ok(Size::Medium),
-            "small" => Ok(Size::Small),
-            _ => Err(PexelsError::ParseSizeError),
-        }
-    }
-}
-
-impl Display for Size {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            Size::Large => "large".to_string(),
-            Size::Medium => "medium".to_string(),
-            Size::Small => "small".to_string(),
-        };
-        write!(f, "{str}")
-    }
-}
+query_enum!(Size, PexelsError::ParseSizeError, {
+    Large => "large",
+    Medium => "medium",
+    Small => "small",
+});
 
 /// Type alias for the result returned by builders.
 pub(crate) type BuilderResult = Result<String, PexelsError>;
@@ -484,6 +361,20 @@ pub enum PexelsError {
     ParseLocaleError,
     #[error("Download error: {0}")]
     DownloadError(String),
+    #[error("Unexpected content type: expected {expected}, found {found}")]
+    UnexpectedContentType { expected: String, found: String },
+    #[error("No suitable video rendition: {0}")]
+    NoSuitableRendition(String),
+    #[error("Integrity check failed: {0}")]
+    IntegrityError(String),
+    #[error("Metadata error: {0}")]
+    MetadataError(String),
+    #[error("Insufficient disk space: {required} bytes required, {available} available")]
+    InsufficientSpace { required: u64, available: u64 },
+    #[error("Download budget of {0} bytes exceeded")]
+    BudgetExceeded(u64),
+    #[error("Response has fields unknown to the models: {}", .0.join(", "))]
+    SchemaDrift(Vec<String>),
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("API error: {0}")]
@@ -494,6 +385,8 @@ pub enum PexelsError {
     AuthError(String),
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
+    #[error("Invalid page URL: {0}")]
+    InvalidPageUrl(String),
     #[error("Resource not found: {0}")]
     NotFound(String),
     #[error("Asynchronous task error")]
@@ -524,6 +417,24 @@ impl PartialEq for PexelsError {
             (PexelsError::HexColorCodeError(msg1), PexelsError::HexColorCodeError(msg2)) => {
                 msg1 == msg2
             }
+            // Compare UnexpectedContentType
+            (
+                PexelsError::UnexpectedContentType { expected: e1, found: f1 },
+                PexelsError::UnexpectedContentType { expected: e2, found: f2 },
+            ) => e1 == e2 && f1 == f2,
+            // Compare InsufficientSpace
+            (
+                PexelsError::InsufficientSpace { required: r1, available: a1 },
+                PexelsError::InsufficientSpace { required: r2, available: a2 },
+            ) => r1 == r2 && a1 == a2,
+            // Compare BudgetExceeded
+            (PexelsError::BudgetExceeded(b1), PexelsError::BudgetExceeded(b2)) => b1 == b2,
+            // Compare InvalidParameter
+            (PexelsError::InvalidParameter(m1), PexelsError::InvalidParameter(m2)) => m1 == m2,
+            // Compare InvalidPageUrl
+            (PexelsError::InvalidPageUrl(m1), PexelsError::InvalidPageUrl(m2)) => m1 == m2,
+            // Compare SchemaDrift
+            (PexelsError::SchemaDrift(f1), PexelsError::SchemaDrift(f2)) => f1 == f2,
             // Other things are not equal
             _ => false,
         }
@@ -568,6 +479,9 @@ impl PartialEq for PexelsError {
 pub struct Pexels {
     client: Client,
     api_key: String,
+    strict: bool,
+    /// Origin that page URLs must have, so the API key is never sent elsewhere
+    api_base: String,
 }
 
 impl Pexels {
@@ -590,7 +504,17 @@ impl Pexels {
     /// }
     /// ```         
     pub fn new(api_key: String) -> Self {
-        Pexels { client: Client::new(), api_key }
+        Pexels { client: Client::new(), api_key, strict: false, api_base: PEXELS_API.to_string() }
+    }
+
+    /// Rejects responses with fields unknown to the models.
+    ///
+    /// Unknown fields are normally kept in the `extra` map of each model. In strict mode
+    /// requests fail with [`PexelsError::SchemaDrift`] listing their paths instead, which
+    /// is useful in tests to notice changes of the API.
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// Sends an HTTP GET request to the specified URL and returns the JSON response.
@@ -610,6 +534,77 @@ impl Pexels {
         Ok(json_response)
     }
 
+    /// Fetches the page at `url`, e.g. a `next_page` link persisted as a cursor.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use pexels_api::{Pexels, PhotosResponse};
+    ///
+    /// # async fn run(client: Pexels) -> Result<(), pexels_api::PexelsError> {
+    /// let url = "https://api.pexels.com/v1/search/?page=2&per_page=15&query=nature";
+    /// let page: PhotosResponse = client.fetch_page(url).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidPageUrl`] if `url` is not a Pexels API URL, so the API key
+    /// is never sent elsewhere, and maps failed requests like the other clients do.
+    pub async fn fetch_page<T: DeserializeOwned + UnknownFields>(
+        &self,
+        url: &str,
+    ) -> Result<T, PexelsError> {
+        let url = page_url::same_origin(url, &self.api_base)?;
+        let response =
+            self.client.get(url.clone()).header("Authorization", &self.api_key).send().await?;
+
+        self.parse(page_url::check_status(response, &url)?.json().await?)
+    }
+
+    /// Fetches the page after `page`, or `None` if it is the last one.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use pexels_api::{Pexels, SearchBuilder};
+    ///
+    /// # async fn run(client: Pexels) -> Result<(), pexels_api::PexelsError> {
+    /// let mut page = client.search_photos(SearchBuilder::new().query("nature")).await?;
+    /// while let Some(next) = client.fetch_next(&page).await? {
+    ///     page = next;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_next<T>(&self, page: &T) -> Result<Option<T>, PexelsError>
+    where
+        T: Paginated + DeserializeOwned + UnknownFields,
+    {
+        match page.next_page_url() {
+            Some(url) => self.fetch_page(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches the page before `page`, or `None` if it is the first one.
+    pub async fn fetch_prev<T>(&self, page: &T) -> Result<Option<T>, PexelsError>
+    where
+        T: Paginated + DeserializeOwned + UnknownFields,
+    {
+        match page.prev_page_url() {
+            Some(url) => self.fetch_page(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Converts a JSON response into a model, checking for unknown fields in strict mode.
+    ///
+    /// # Errors
+    /// Returns a `PexelsError` if the response doesn't match the model, or has unknown fields
+    /// in strict mode.
+    fn parse<T: DeserializeOwned + UnknownFields>(&self, value: Value) -> Result<T, PexelsError> {
+        schema::check(serde_json::from_value(value)?, self.strict)
+    }
+
     /// Retrieves a list of photos from the Pexels API based on the search criteria.
     ///
     /// # Arguments
@@ -638,7 +633,7 @@ impl Pexels {
         &self,
         builder: SearchBuilder<'_>,
     ) -> Result<PhotosResponse, PexelsError> {
-        builder.build().fetch(self).await
+        builder.try_build()?.fetch(self).await
     }
 
     /// Retrieves a photo by its ID from the Pexels API.
@@ -664,8 +659,8 @@ impl Pexels {
     ///     println!("{:?}", response);
     /// }
     /// ```                
-    pub async fn get_photo(&self, id: usize) -> Result<Photo, PexelsError> {
-        FetchPhotoBuilder::new().id(id).build().fetch(self).await
+    pub async fn get_photo(&self, id: impl Into<PhotoId>) -> Result<Photo, PexelsError> {
+        FetchPhotoBuilder::new().id(id).try_build()?.fetch(self).await
     }
 
     /// Retrieves a random photo from the Pexels API.
@@ -696,7 +691,7 @@ impl Pexels {
         &self,
         builder: CuratedBuilder,
     ) -> Result<PhotosResponse, PexelsError> {
-        builder.build().fetch(self).await
+        builder.try_build()?.fetch(self).await
     }
 
     /// Retrieves a list of videos from the Pexels API based on the search criteria.
@@ -727,7 +722,7 @@ impl Pexels {
         &self,
         builder: VideoSearchBuilder<'_>,
     ) -> Result<VideoResponse, PexelsError> {
-        builder.build().fetch(self).await
+        builder.try_build()?.fetch(self).await
     }
 
     /// Retrieves a list of popular videos from the Pexels API.
@@ -758,7 +753,7 @@ impl Pexels {
         &self,
         builder: PopularBuilder,
     ) -> Result<VideoResponse, PexelsError> {
-        builder.build().fetch(self).await
+        builder.try_build()?.fetch(self).await
     }
 
     /// Retrieves a video by its ID from the Pexels API.
@@ -784,8 +779,8 @@ impl Pexels {
     ///     println!("{:?}", response);
     /// }
     /// ```
-    pub async fn get_video(&self, id: usize) -> Result<Video, PexelsError> {
-        FetchVideoBuilder::new().id(id).build().fetch(self).await
+    pub async fn get_video(&self, id: impl Into<VideoId>) -> Result<Video, PexelsError> {
+        FetchVideoBuilder::new().id(id).try_build()?.fetch(self).await
     }
 
     /// Retrieves a list of collections from the Pexels API.
@@ -817,7 +812,7 @@ impl Pexels {
         per_page: usize,
         page: usize,
     ) -> Result<CollectionsResponse, PexelsError> {
-        CollectionsBuilder::new().per_page(per_page).page(page).build().fetch(self).await
+        CollectionsBuilder::new().per_page(per_page).page(page).try_build()?.fetch(self).await
     }
 
     /// Retrieves a list of featured collections from the Pexels API.
@@ -849,7 +844,7 @@ impl Pexels {
         per_page: usize,
         page: usize,
     ) -> Result<CollectionsResponse, PexelsError> {
-        FeaturedBuilder::new().per_page(per_page).page(page).build().fetch(self).await
+        FeaturedBuilder::new().per_page(per_page).page(page).try_build()?.fetch(self).await
     }
 
     /// Retrieves all media (photos and videos) within a single collection.
@@ -878,7 +873,7 @@ impl Pexels {
     /// }                 
     /// ```
     pub async fn search_media(&self, builder: MediaBuilder) -> Result<MediaResponse, PexelsError> {
-        builder.build().fetch(self).await
+        builder.try_build()?.fetch(self).await
     }
 }
 
@@ -928,6 +923,84 @@ mod tests {
         assert!(matches!(media_type, Err(PexelsError::ParseMediaTypeError)));
     }
 
+    #[test]
+    fn test_query_enums_round_trip() {
+        fn round_trip<T>(all: &[T])
+        where
+            T: FromStr<Err = PexelsError> + Display + Serialize + for<'de> Deserialize<'de>,
+            T: PartialEq + std::fmt::Debug,
+        {
+            for value in all {
+                let text = value.to_string();
+                assert_eq!(text.parse::<T>().unwrap(), *value);
+                assert_eq!(text.to_uppercase().parse::<T>().unwrap(), *value);
+                let json = serde_json::to_string(value).unwrap();
+                assert_eq!(json, format!("\"{text}\""));
+                assert_eq!(serde_json::from_str::<T>(&json).unwrap(), *value);
+            }
+        }
+
+        round_trip(Orientation::ALL);
+        round_trip(Size::ALL);
+        round_trip(MediaSort::ALL);
+        round_trip(MediaType::ALL);
+        assert_eq!(MediaType::ALL, &[MediaType::Photo, MediaType::Video]);
+        assert_eq!("".parse::<MediaType>().unwrap(), MediaType::Empty);
+        assert_eq!(MediaType::Empty.to_string(), "");
+        assert_eq!(MediaSort::Desc.to_string(), "desc");
+        assert_eq!(serde_json::from_str::<MediaType>("\"video\"").unwrap(), MediaType::Video);
+    }
+
+    #[test]
+    fn test_query_enum_parse_errors() {
+        assert!(matches!(
+            "diagonal".parse::<Orientation>(),
+            Err(PexelsError::ParseOrientationError)
+        ));
+        assert!(matches!("huge".parse::<Size>(), Err(PexelsError::ParseSizeError)));
+        assert!(matches!("random".parse::<MediaSort>(), Err(PexelsError::ParseMediaSortError)));
+        assert!(serde_json::from_str::<Size>("\"huge\"").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_next_and_prev() {
+        use crate::test_support::{json_response, response, serve_base_url};
+        use serde_json::json;
+
+        let page = |page: u32, prev_page: Option<String>, next_page: Option<String>| {
+            json!({
+                "page": page, "per_page": 1, "total_results": 3, "photos": [],
+                "prev_page": prev_page, "next_page": next_page,
+            })
+        };
+        let api_base = serve_base_url(vec![
+            json_response(&page(
+                2,
+                Some("http://127.0.0.1:1/v1/curated/?page=1".to_string()),
+                None,
+            )),
+            response("429 Too Many Requests", b""),
+        ])
+        .await;
+        let client = Pexels {
+            client: Client::builder().no_proxy().build().unwrap(),
+            api_key: "key".to_string(),
+            strict: false,
+            api_base: api_base.clone(),
+        };
+
+        let next = Some(format!("{api_base}/v1/curated/?page=2"));
+        let first: PhotosResponse = serde_json::from_value(page(1, None, next)).unwrap();
+        assert!(client.fetch_prev(&first).await.unwrap().is_none());
+        let second = client.fetch_next(&first).await.unwrap().unwrap();
+        assert_eq!(second.page, 2);
+        assert!(client.fetch_next(&second).await.unwrap().is_none());
+
+        // The previous page is on another port, so the API key must not be sent there
+        assert!(matches!(client.fetch_prev(&second).await, Err(PexelsError::InvalidPageUrl(_))));
+        assert!(matches!(client.fetch_next(&first).await, Err(PexelsError::RateLimitError)));
+    }
+
     #[tokio::test]
     #[ignore = "requires PEXELS_API_KEY and live Pexels API access"]
     async fn test_make_request() {
@@ -0,0 +1,207 @@
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::PexelsError;
+
+/// Declares [`Locale`] and its tags from a single table
+macro_rules! locales {
+    ($($variant:ident => $tag:literal,)*) => {
+        /// Specifies the locale for the search query.
+        /// Supported values: `en-US`, `pt-BR`, `es-ES`, `ca-ES`, `de-DE`, `it-IT`, `fr-FR`, `sv-SE`, `id-ID`, `pl-PL`, `ja-JP`, `zh-CN`, `zh-TW`, `ko-KR`, `th-TH`, `nl-NL`, `hu-HU`, `vi-VN`, `cs-CZ`, `da-DK`, `fi-FI`, `uk-UA`, `el-GR`, `ro-RO`, `nb-NO`, `sk-SK`, `tr-TR`, `ru-RU`.
+        /// Default: `en-US`.
+        ///
+        /// Parsing accepts BCP-47 tags and POSIX locale names in any case, e.g. `ru-RU`,
+        /// `ru_RU` and `ru_RU.UTF-8`. A bare language such as `ru`, or a region Pexels
+        /// doesn't support such as `en-GB`, resolves to the first locale of that language
+        /// in the list above.
+        ///
+        /// # Example
+        /// ```rust
+        /// use pexels_api::Locale;
+        /// use std::str::FromStr;
+        ///
+        /// let locale = Locale::from_str("en-US").unwrap();
+        /// assert_eq!(locale, Locale::en_US);
+        /// assert_eq!("ru".parse::<Locale>().unwrap(), Locale::ru_RU);
+        /// assert_eq!(Locale::pt_BR.to_string(), "pt-BR");
+        /// ```
+        #[allow(non_camel_case_types)]
+        #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+        pub enum Locale {
+            #[default]
+            $($variant,)*
+        }
+
+        impl Locale {
+            /// Every supported locale
+            pub const ALL: &'static [Locale] = &[$(Locale::$variant,)*];
+
+            /// The BCP-47 tag of the locale, e.g. `en-US`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Locale::$variant => $tag,)*
+                }
+            }
+        }
+    };
+}
+
+locales! {
+    en_US => "en-US",
+    pt_BR => "pt-BR",
+    es_ES => "es-ES",
+    ca_ES => "ca-ES",
+    de_DE => "de-DE",
+    it_IT => "it-IT",
+    fr_FR => "fr-FR",
+    sv_SE => "sv-SE",
+    id_ID => "id-ID",
+    pl_PL => "pl-PL",
+    ja_JP => "ja-JP",
+    zh_CN => "zh-CN",
+    zh_TW => "zh-TW",
+    ko_KR => "ko-KR",
+    th_TH => "th-TH",
+    nl_NL => "nl-NL",
+    hu_HU => "hu-HU",
+    vi_VN => "vi-VN",
+    cs_CZ => "cs-CZ",
+    da_DK => "da-DK",
+    fi_FI => "fi-FI",
+    uk_UA => "uk-UA",
+    el_GR => "el-GR",
+    ro_RO => "ro-RO",
+    nb_NO => "nb-NO",
+    sk_SK => "sk-SK",
+    tr_TR => "tr-TR",
+    ru_RU => "ru-RU",
+}
+
+impl Locale {
+    /// The language part of the tag, e.g. `en`
+    pub fn language(&self) -> &'static str {
+        self.as_str().split('-').next().unwrap_or_default()
+    }
+
+    /// The region part of the tag, e.g. `US`
+    pub fn region(&self) -> &'static str {
+        self.as_str().split('-').nth(1).unwrap_or_default()
+    }
+
+    /// Resolve the locale from `LC_ALL`, or from `LANG` if `LC_ALL` is unset or empty
+    ///
+    /// As in POSIX a non-empty `LC_ALL` takes precedence even when it names no supported
+    /// language, so `None` is returned for `LC_ALL=C` or `POSIX` whatever `LANG` is.
+    pub fn from_env() -> Option<Locale> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Locale> {
+        let value =
+            ["LC_ALL", "LANG"].into_iter().filter_map(var).find(|value| !value.is_empty())?;
+        value.parse().ok()
+    }
+}
+
+impl FromStr for Locale {
+    type Err = PexelsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Drop the encoding and modifier of POSIX names like `de_DE.UTF-8@euro`
+        let tag = s.split(['.', '@']).next().unwrap_or_default().trim();
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next().unwrap_or_default();
+        // Skip a script subtag such as `Hans` in `zh-Hans-CN`
+        let region = subtags.find(|subtag| subtag.len() == 2);
+
+        let same_language = |locale: &&Locale| locale.language().eq_ignore_ascii_case(language);
+        let exact = region.and_then(|region| {
+            Locale::ALL
+                .iter()
+                .filter(same_language)
+                .find(|locale| locale.region().eq_ignore_ascii_case(region))
+        });
+        exact
+            .or_else(|| Locale::ALL.iter().find(same_language))
+            .copied()
+            .ok_or(PexelsError::ParseLocaleError)
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_locale_round_trips() {
+        assert_eq!(Locale::ALL.len(), 28);
+        for &locale in Locale::ALL {
+            let tag = locale.as_str();
+            assert_eq!(tag.parse::<Locale>().unwrap(), locale);
+            assert_eq!(tag.replace('-', "_").to_lowercase().parse::<Locale>().unwrap(), locale);
+            assert_eq!(locale.to_string(), tag);
+
+            let json = serde_json::to_string(&locale).unwrap();
+            assert_eq!(json, format!("\"{tag}\""));
+            assert_eq!(serde_json::from_str::<Locale>(&json).unwrap(), locale);
+        }
+    }
+
+    #[test]
+    fn test_bcp47_and_posix_names() {
+        assert_eq!(Locale::ru_RU.as_str(), "ru-RU");
+        for name in ["ru", "ru_RU", "ru-RU", "RU-ru", "ru_RU.UTF-8"] {
+            assert_eq!(name.parse::<Locale>().unwrap(), Locale::ru_RU, "{name}");
+        }
+        assert_eq!("en-GB".parse::<Locale>().unwrap(), Locale::en_US);
+        assert_eq!("zh".parse::<Locale>().unwrap(), Locale::zh_CN);
+        assert_eq!("zh-Hant-TW".parse::<Locale>().unwrap(), Locale::zh_TW);
+        assert_eq!("de_DE.ISO-8859-15@euro".parse::<Locale>().unwrap(), Locale::de_DE);
+        assert!(matches!("C".parse::<Locale>(), Err(PexelsError::ParseLocaleError)));
+        assert!(matches!("".parse::<Locale>(), Err(PexelsError::ParseLocaleError)));
+    }
+
+    fn vars(
+        lc_all: Option<&'static str>,
+        lang: Option<&'static str>,
+    ) -> impl Fn(&str) -> Option<String> {
+        move |name| match name {
+            "LC_ALL" => lc_all.map(str::to_string),
+            "LANG" => lang.map(str::to_string),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_from_env_vars() {
+        assert_eq!(
+            Locale::from_vars(vars(Some("fr_FR.UTF-8"), Some("de_DE"))),
+            Some(Locale::fr_FR)
+        );
+        assert_eq!(Locale::from_vars(vars(Some("C"), Some("ja_JP.UTF-8"))), None);
+        assert_eq!(Locale::from_vars(vars(Some(""), Some("ja_JP.UTF-8"))), Some(Locale::ja_JP));
+        assert_eq!(Locale::from_vars(vars(None, Some("POSIX"))), None);
+        assert_eq!(Locale::from_vars(vars(None, None)), None);
+    }
+}
@@ -1,19 +1,29 @@
-use crate::{User, VideoPicture};
+use crate::{
+    CollectionId, MediaMime, PhotoId, User, UserId, VideoFileQuality, VideoId, VideoPicture,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::schema::{null_as_default, unknown_fields, UnknownFields};
 
 /// Photo
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Photo {
-    pub id: u64,
+    pub id: PhotoId,
     pub width: u32,
     pub height: u32,
     pub url: String,
     pub photographer: String,
     pub photographer_url: Option<String>,
-    pub photographer_id: Option<u64>,
+    pub photographer_id: Option<UserId>,
     pub avg_color: Option<String>,
     pub src: PhotoSources,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub liked: bool,
     pub alt: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,30 +36,47 @@ pub struct PhotoSources {
     pub portrait: String,
     pub landscape: String,
     pub tiny: String,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Video
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Video {
-    pub id: u64,
+    pub id: VideoId,
     pub width: u32,
     pub height: u32,
     pub url: String,
     pub image: String,
     pub duration: u32,
+    pub full_res: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub tags: Vec<String>,
+    pub avg_color: Option<String>,
     pub user: User,
     pub video_files: Vec<VideoFile>,
     pub video_pictures: Vec<VideoPicture>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoFile {
     pub id: u64,
-    pub quality: String,
-    pub file_type: String,
+    #[serde(default)]
+    pub quality: Option<VideoFileQuality>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub file_type: MediaMime,
     pub width: Option<u32>,
     pub height: Option<u32>,
+    pub fps: Option<f64>,
+    pub size: Option<u64>,
     pub link: String,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Video picture
@@ -61,6 +88,9 @@ pub struct PhotosPage {
     pub total_results: u32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,13 +101,16 @@ pub struct VideosPage {
     pub total_results: u32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Collection media model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     /// Collection ID
-    pub id: String,
+    pub id: CollectionId,
 
     /// Collection title
     pub title: String,
@@ -96,6 +129,10 @@ pub struct Collection {
 
     /// Collection videos count
     pub videos_count: u32,
+
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Collections page response
@@ -118,6 +155,10 @@ pub struct CollectionsPage {
 
     /// URL to the previous page
     pub prev_page: Option<String>,
+
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Media item type
@@ -137,7 +178,7 @@ pub enum MediaItem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaPage {
     /// ID of the collection
-    pub id: String,
+    pub id: CollectionId,
 
     /// Media items in this page
     pub media: Vec<MediaItem>,
@@ -156,4 +197,27 @@ pub struct MediaPage {
 
     /// URL to the previous page
     pub prev_page: Option<String>,
+
+    /// Fields not known to this version of the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+unknown_fields!(Photo, src);
+unknown_fields!(PhotoSources);
+unknown_fields!(Video, user, video_files, video_pictures);
+unknown_fields!(VideoFile);
+unknown_fields!(PhotosPage, photos);
+unknown_fields!(VideosPage, videos);
+unknown_fields!(Collection);
+unknown_fields!(CollectionsPage, collections);
+unknown_fields!(MediaPage, media);
+
+impl UnknownFields for MediaItem {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        match self {
+            MediaItem::Photo(photo) => photo.collect_unknown_fields(path, fields),
+            MediaItem::Video(video) => video.collect_unknown_fields(path, fields),
+        }
+    }
 }
@@ -0,0 +1,242 @@
+//! Typed requests from the `next_page` and `prev_page` URLs of pages
+//!
+//! Pages link to their neighbours with full API URLs, e.g.
+//! `https://api.pexels.com/v1/search/?page=2&per_page=15&query=nature`. [`Paginated`] exposes
+//! those links so the clients can follow them, and the `from_url` constructors of the
+//! requests turn such a URL back into a typed request, so a cursor can be persisted as a URL
+//! and resumed later.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use reqwest::{Response, StatusCode};
+use url::Url;
+
+use crate::{CollectionId, PexelsError};
+
+/// Pages that link to the next and previous page
+pub trait Paginated {
+    /// URL of the next page, if there is one
+    fn next_page_url(&self) -> Option<&str>;
+
+    /// URL of the previous page, if there is one
+    fn prev_page_url(&self) -> Option<&str>;
+}
+
+/// Implement [`Paginated`] for pages with `next_page` and `prev_page` fields
+macro_rules! paginated {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Paginated for $ty {
+                fn next_page_url(&self) -> Option<&str> {
+                    self.next_page.as_deref()
+                }
+
+                fn prev_page_url(&self) -> Option<&str> {
+                    self.prev_page.as_deref()
+                }
+            }
+        )*
+    };
+}
+
+paginated!(
+    crate::PhotosResponse,
+    crate::VideoResponse,
+    crate::CollectionsResponse,
+    crate::MediaResponse,
+    crate::models::PhotosPage,
+    crate::models::VideosPage,
+    crate::models::CollectionsPage,
+    crate::models::MediaPage,
+);
+
+/// Endpoints of the API that return pages
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Endpoint {
+    PhotoSearch,
+    Curated,
+    VideoSearch,
+    PopularVideos,
+    Collections,
+    FeaturedCollections,
+    CollectionMedia(CollectionId),
+}
+
+impl Endpoint {
+    fn name(&self) -> &'static str {
+        match self {
+            Endpoint::PhotoSearch => "photo search",
+            Endpoint::Curated => "curated photos",
+            Endpoint::VideoSearch => "video search",
+            Endpoint::PopularVideos => "popular videos",
+            Endpoint::Collections => "collections",
+            Endpoint::FeaturedCollections => "featured collections",
+            Endpoint::CollectionMedia(_) => "collection media",
+        }
+    }
+}
+
+/// An API URL split into its endpoint and query parameters
+pub(crate) struct PageUrl {
+    url: String,
+    endpoint: Endpoint,
+    query: Vec<(String, String)>,
+}
+
+impl PageUrl {
+    /// Parse any URL of an endpoint that returns pages
+    ///
+    /// Only the path is checked, so URLs of other hosts such as a test server are accepted.
+    pub(crate) fn parse(url: &str) -> Result<Self, PexelsError> {
+        let parsed = Url::parse(url)?;
+        let segments: Vec<&str> = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+            .unwrap_or_default();
+        let endpoint = match segments.as_slice() {
+            [.., "videos", "search"] => Endpoint::VideoSearch,
+            [.., "videos", "popular"] => Endpoint::PopularVideos,
+            [.., "search"] => Endpoint::PhotoSearch,
+            [.., "curated"] => Endpoint::Curated,
+            [.., "collections", "featured"] => Endpoint::FeaturedCollections,
+            [.., "collections"] => Endpoint::Collections,
+            [.., "collections", id] => Endpoint::CollectionMedia(CollectionId::new(*id)),
+            _ => {
+                return Err(PexelsError::InvalidPageUrl(format!(
+                    "{url} is not an endpoint that returns pages"
+                )))
+            }
+        };
+        let query = parsed.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+        Ok(Self { url: url.to_string(), endpoint, query })
+    }
+
+    /// Parse a URL of `endpoint`
+    pub(crate) fn expect(url: &str, endpoint: Endpoint) -> Result<Self, PexelsError> {
+        let page = Self::parse(url)?;
+        if page.endpoint != endpoint {
+            return Err(page.not_an(&endpoint));
+        }
+        Ok(page)
+    }
+
+    /// The id of a collection media URL
+    pub(crate) fn collection_id(&self) -> Result<CollectionId, PexelsError> {
+        match &self.endpoint {
+            Endpoint::CollectionMedia(id) => Ok(id.clone()),
+            _ => Err(self.not_an(&Endpoint::CollectionMedia(CollectionId::default()))),
+        }
+    }
+
+    /// The last value of the query parameter `key`, parsed as `T`
+    pub(crate) fn get<T>(&self, key: &str) -> Result<Option<T>, PexelsError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.query
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| {
+                value.parse().map_err(|e| {
+                    PexelsError::InvalidPageUrl(format!("{key}={value} in {}: {e}", self.url))
+                })
+            })
+            .transpose()
+    }
+
+    /// The search query, which search URLs must have
+    pub(crate) fn search_query(&self) -> Result<String, PexelsError> {
+        self.get("query")?.ok_or_else(|| {
+            PexelsError::InvalidPageUrl(format!("{} has no query parameter", self.url))
+        })
+    }
+
+    fn not_an(&self, endpoint: &Endpoint) -> PexelsError {
+        PexelsError::InvalidPageUrl(format!("{} is not a {} URL", self.url, endpoint.name()))
+    }
+}
+
+/// Parse `url`, which must have the same scheme, host and port as `base`
+///
+/// Clients check page URLs before following them, so the API key is never sent elsewhere.
+pub(crate) fn same_origin(url: &str, base: &str) -> Result<Url, PexelsError> {
+    let parsed = Url::parse(url)?;
+    if parsed.origin() != Url::parse(base)?.origin() {
+        return Err(PexelsError::InvalidPageUrl(format!("{url} is not a URL of {base}")));
+    }
+    Ok(parsed)
+}
+
+/// Map a failed response to the page at `url` to an error, as both clients do
+pub(crate) fn check_status(response: Response, url: &Url) -> Result<Response, PexelsError> {
+    match response.status() {
+        StatusCode::OK => Ok(response),
+        StatusCode::NOT_FOUND => Err(PexelsError::NotFound(format!("Page {url} not found"))),
+        StatusCode::UNAUTHORIZED => Err(PexelsError::AuthError("Invalid API key".to_string())),
+        StatusCode::TOO_MANY_REQUESTS => Err(PexelsError::RateLimitError),
+        status => Err(PexelsError::ApiError(format!("Fetch page failed with status: {status}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoints() {
+        let endpoint = |url: &str| PageUrl::parse(url).map(|page| page.endpoint);
+        assert_eq!(
+            endpoint("https://api.pexels.com/v1/search/?page=2&query=nature"),
+            Ok(Endpoint::PhotoSearch)
+        );
+        assert_eq!(
+            endpoint("https://api.pexels.com/videos/search?page=2"),
+            Ok(Endpoint::VideoSearch)
+        );
+        assert_eq!(endpoint("https://api.pexels.com/v1/curated/"), Ok(Endpoint::Curated));
+        assert_eq!(
+            endpoint("https://api.pexels.com/v1/collections/featured?page=3"),
+            Ok(Endpoint::FeaturedCollections)
+        );
+        assert_eq!(
+            endpoint("https://api.pexels.com/v1/collections/8xntbhr?page=2"),
+            Ok(Endpoint::CollectionMedia(CollectionId::new("8xntbhr")))
+        );
+        assert!(matches!(
+            endpoint("https://api.pexels.com/v1/photos/2014422"),
+            Err(PexelsError::InvalidPageUrl(_))
+        ));
+        assert!(matches!(endpoint("not a url"), Err(PexelsError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_query_values() {
+        let page = PageUrl::expect(
+            "https://api.pexels.com/v1/curated?page=1&per_page=x&page=4",
+            Endpoint::Curated,
+        )
+        .unwrap();
+        assert_eq!(page.get::<usize>("page"), Ok(Some(4)));
+        assert_eq!(page.get::<usize>("size"), Ok(None));
+        assert!(matches!(page.get::<usize>("per_page"), Err(PexelsError::InvalidPageUrl(_))));
+        assert!(page.search_query().is_err());
+        assert!(page.collection_id().is_err());
+        assert_eq!(
+            PageUrl::expect("https://api.pexels.com/v1/curated", Endpoint::PhotoSearch).err(),
+            Some(PexelsError::InvalidPageUrl(
+                "https://api.pexels.com/v1/curated is not a photo search URL".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_same_origin() {
+        let base = "https://api.pexels.com/v1";
+        assert!(same_origin("https://api.pexels.com/videos/search?page=2", base).is_ok());
+        assert!(same_origin("http://api.pexels.com/v1/search", base).is_err());
+        assert!(same_origin("https://example.com/v1/search", base).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{Pexels, PexelsError, PhotosResponse, PEXELS_API, PEXELS_VERSION};
+use crate::page_url::{Endpoint, PageUrl};
+use crate::{validate, Pexels, PexelsError, PhotosResponse, PEXELS_API, PEXELS_VERSION};
 use url::Url;
 
 /// Pexels API path for curated photos
@@ -16,6 +17,13 @@ impl Curated {
         CuratedBuilder::default()
     }
 
+    /// Parses a curated photos URL, such as the `next_page` of a [`PhotosResponse`], back into
+    /// a [`Curated`].
+    pub fn from_url(url: &str) -> Result<Curated, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::Curated)?;
+        Ok(Curated { page: url.get("page")?, per_page: url.get("per_page")? })
+    }
+
     /// Create URI from inputted vales from the [`CuratedBuilder`].
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!("{PEXELS_API}/{PEXELS_VERSION}/{PEXELS_CURATED_PATH}");
@@ -37,7 +45,7 @@ impl Curated {
     pub async fn fetch(&self, client: &Pexels) -> Result<PhotosResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let photos_response: PhotosResponse = serde_json::from_value(response)?;
+        let photos_response: PhotosResponse = client.parse(response)?;
         Ok(photos_response)
     }
 }
@@ -71,12 +79,32 @@ impl CuratedBuilder {
     pub fn build(self) -> Curated {
         Curated { page: self.page, per_page: self.per_page }
     }
+
+    /// Builds [`Curated`] like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Curated, PexelsError> {
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_url() {
+        let curated = Curated::from_url("https://api.pexels.com/v1/curated/?page=2&per_page=1");
+        assert_eq!(
+            curated.unwrap().create_uri().unwrap(),
+            "https://api.pexels.com/v1/curated?page=2&per_page=1"
+        );
+        assert!(Curated::from_url("https://api.pexels.com/v1/curated?page=two").is_err());
+    }
+
     #[test]
     fn test_page() {
         let uri = CuratedBuilder::new().page(1).build();
@@ -1,4 +1,4 @@
-use crate::{Pexels, PexelsError, Photo, PEXELS_API, PEXELS_VERSION};
+use crate::{validate, Pexels, PexelsError, Photo, PhotoId, PEXELS_API, PEXELS_VERSION};
 use url::Url;
 
 /// Path to get a specific photo.
@@ -6,7 +6,7 @@ const PEXELS_GET_PHOTO_PATH: &str = "photos";
 
 /// Retrieve a specific Photo from its id.
 pub struct FetchPhoto {
-    id: usize,
+    id: PhotoId,
 }
 
 impl FetchPhoto {
@@ -29,7 +29,7 @@ impl FetchPhoto {
     pub async fn fetch(&self, client: &Pexels) -> Result<Photo, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let photo: Photo = serde_json::from_value(response)?;
+        let photo: Photo = client.parse(response)?;
         Ok(photo)
     }
 }
@@ -37,18 +37,18 @@ impl FetchPhoto {
 /// Builder for [`FetchPhoto`].
 #[derive(Default)]
 pub struct FetchPhotoBuilder {
-    id: usize,
+    id: PhotoId,
 }
 
 impl FetchPhotoBuilder {
     /// Create a new [`FetchPhotoBuilder`].
     pub fn new() -> Self {
-        Self { id: 0 }
+        Self { id: PhotoId::default() }
     }
 
     /// Sets the ID of the photo to be requested.
-    pub fn id(mut self, id: usize) -> Self {
-        self.id = id;
+    pub fn id(mut self, id: impl Into<PhotoId>) -> Self {
+        self.id = id.into();
         self
     }
 
@@ -56,6 +56,15 @@ impl FetchPhotoBuilder {
     pub fn build(self) -> FetchPhoto {
         FetchPhoto { id: self.id }
     }
+
+    /// Builds [`FetchPhoto`] like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if the id is 0.
+    pub fn try_build(self) -> Result<FetchPhoto, PexelsError> {
+        validate::id(self.id.get())?;
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,11 @@
+use crate::page_url::{Endpoint, PageUrl};
 use crate::{
-    Locale, Orientation, Pexels, PexelsError, PhotosResponse, Size, PEXELS_API, PEXELS_VERSION,
+    validate, Locale, Orientation, Pexels, PexelsError, PhotosResponse, Size, PEXELS_API,
+    PEXELS_VERSION,
 };
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::str::FromStr;
 use url::Url;
 const PEXELS_PHOTO_SEARCH_PATH: &str = "search";
 
@@ -25,13 +30,16 @@ const PEXELS_PHOTO_SEARCH_PATH: &str = "search";
 ///
 /// # Errors
 /// Returns [`PexelsError::HexColorCodeError`] if the string is not a valid hexadecimal color code.
-#[derive(Debug, PartialEq)]
-pub struct Hex<'a>(&'a str);
+///
+/// An owned [`Hex`] can be created from a `String` with [`Hex::new`] or from an
+/// [`RgbColor`](crate::RgbColor).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hex<'a>(Cow<'a, str>);
 
 impl<'a> Hex<'a> {
-    /// Create a new [`Hex`] from a string literal.
-    #[allow(clippy::should_implement_trait)]
-    pub fn from_borrowed_str(v: &'a str) -> Result<Self, PexelsError> {
+    /// Create a new [`Hex`] from a borrowed or owned string.
+    pub fn new(v: impl Into<Cow<'a, str>>) -> Result<Self, PexelsError> {
+        let v = v.into();
         if v.len() != 7 {
             return Err(PexelsError::HexColorCodeError(format!("{v} is not 7 characters long.")));
         }
@@ -49,9 +57,45 @@ impl<'a> Hex<'a> {
 
         Ok(Self(v))
     }
+
+    /// Create a new [`Hex`] from a string literal.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_borrowed_str(v: &'a str) -> Result<Self, PexelsError> {
+        Self::new(v)
+    }
+
+    /// Create a new [`Hex`] from an RGB color.
+    pub(crate) fn from_rgb(color: crate::RgbColor) -> Hex<'static> {
+        Hex(Cow::Owned(color.to_hex()))
+    }
+
+    /// Returns the color code, e.g. `#FFFFFF`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts into a [`Hex`] that owns its color code.
+    pub fn into_owned(self) -> Hex<'static> {
+        Hex(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl Serialize for Hex<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Hex<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Hex::new(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
 }
 
 /// Represents the desired photo color.
+///
+/// Serializes as its query value, e.g. `pink` or `#FFFFFF`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Color<'a> {
     Red,
     Orange,
@@ -70,8 +114,8 @@ pub enum Color<'a> {
 
 impl Color<'_> {
     /// Returns the string representation of the color.
-    fn as_str(&self) -> Result<&str, PexelsError> {
-        let value = match self {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
             Color::Red => "red",
             Color::Orange => "orange",
             Color::Yellow => "yellow",
@@ -84,16 +128,70 @@ impl Color<'_> {
             Color::Black => "black",
             Color::Gray => "gray",
             Color::White => "white",
-            Color::Hex(v) => v.0,
-        };
+            Color::Hex(v) => v.as_str(),
+        }
+    }
+
+    /// Converts into a [`Color`] that owns its hex color code.
+    pub fn into_owned(self) -> Color<'static> {
+        match self {
+            Color::Red => Color::Red,
+            Color::Orange => Color::Orange,
+            Color::Yellow => Color::Yellow,
+            Color::Green => Color::Green,
+            Color::Turquoise => Color::Turquoise,
+            Color::Blue => Color::Blue,
+            Color::Violet => Color::Violet,
+            Color::Pink => Color::Pink,
+            Color::Brown => Color::Brown,
+            Color::Black => Color::Black,
+            Color::Gray => Color::Gray,
+            Color::White => Color::White,
+            Color::Hex(v) => Color::Hex(v.into_owned()),
+        }
+    }
+}
 
-        Ok(value)
+impl FromStr for Color<'static> {
+    type Err = PexelsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "red" => Ok(Color::Red),
+            "orange" => Ok(Color::Orange),
+            "yellow" => Ok(Color::Yellow),
+            "green" => Ok(Color::Green),
+            "turquoise" => Ok(Color::Turquoise),
+            "blue" => Ok(Color::Blue),
+            "violet" => Ok(Color::Violet),
+            "pink" => Ok(Color::Pink),
+            "brown" => Ok(Color::Brown),
+            "black" => Ok(Color::Black),
+            "gray" => Ok(Color::Gray),
+            "white" => Ok(Color::White),
+            _ => Hex::new(s.to_string()).map(Color::Hex),
+        }
+    }
+}
+
+impl Serialize for Color<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
     }
 }
 
 /// Represents a search query to the Pexels API.
+///
+/// Like [`SearchBuilder`] it can be serialized, e.g. to persist a search and replay it later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Search<'a> {
-    query: &'a str,
+    query: Cow<'a, str>,
     page: Option<usize>,
     per_page: Option<usize>,
     orientation: Option<Orientation>,
@@ -108,12 +206,31 @@ impl<'a> Search<'a> {
         SearchBuilder::default()
     }
 
+    /// Parses a photo search URL, such as the `next_page` of a [`PhotosResponse`], back into a
+    /// [`Search`].
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidPageUrl`] if the URL is not a photo search URL or has
+    /// invalid parameters.
+    pub fn from_url(url: &str) -> Result<Search<'static>, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::PhotoSearch)?;
+        Ok(Search {
+            query: Cow::Owned(url.search_query()?),
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            orientation: url.get("orientation")?,
+            size: url.get("size")?,
+            color: url.get("color")?,
+            locale: url.get("locale")?,
+        })
+    }
+
     /// Creates a URI from the search parameters. [`SearchBuilder`].
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!("{PEXELS_API}/{PEXELS_VERSION}/{PEXELS_PHOTO_SEARCH_PATH}");
 
         let mut url = Url::parse(uri.as_str())?;
-        url.query_pairs_mut().append_pair("query", self.query);
+        url.query_pairs_mut().append_pair("query", &self.query);
 
         if let Some(page) = &self.page {
             url.query_pairs_mut().append_pair("page", page.to_string().as_str());
@@ -132,7 +249,7 @@ impl<'a> Search<'a> {
         }
 
         if let Some(color) = &self.color {
-            url.query_pairs_mut().append_pair("color", color.as_str()?);
+            url.query_pairs_mut().append_pair("color", color.as_str());
         }
 
         if let Some(locale) = &self.locale {
@@ -146,15 +263,31 @@ impl<'a> Search<'a> {
     pub async fn fetch(&self, client: &Pexels) -> Result<PhotosResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let photos_response: PhotosResponse = serde_json::from_value(response)?;
+        let photos_response: PhotosResponse = client.parse(response)?;
         Ok(photos_response)
     }
 }
 
 /// Builder for [`Search`].
-#[derive(Default)]
+///
+/// The query and hex color may be borrowed or owned; a `SearchBuilder<'static>` is `Send` and
+/// can be stored, serialized and deserialized.
+///
+/// # Example
+///
+/// ```
+/// use pexels_api::{Color, SearchBuilder};
+///
+/// let query = String::from("mountains");
+/// let builder = SearchBuilder::new().query(query).color(Color::Blue).per_page(20);
+/// let json = serde_json::to_string(&builder).unwrap();
+/// let replayed: SearchBuilder<'static> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(replayed.build().create_uri().unwrap(), builder.build().create_uri().unwrap());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchBuilder<'a> {
-    query: &'a str,
+    query: Cow<'a, str>,
     page: Option<usize>,
     per_page: Option<usize>,
     orientation: Option<Orientation>,
@@ -167,7 +300,7 @@ impl<'a> SearchBuilder<'a> {
     /// Creates a new [`SearchBuilder`].
     pub fn new() -> Self {
         Self {
-            query: "",
+            query: Cow::Borrowed(""),
             page: None,
             per_page: None,
             orientation: None,
@@ -178,8 +311,8 @@ impl<'a> SearchBuilder<'a> {
     }
 
     /// Sets the search query.
-    pub fn query(mut self, query: &'a str) -> Self {
-        self.query = query;
+    pub fn query(mut self, query: impl Into<Cow<'a, str>>) -> Self {
+        self.query = query.into();
         self
     }
 
@@ -231,12 +364,59 @@ impl<'a> SearchBuilder<'a> {
             locale: self.locale,
         }
     }
+
+    /// Builds [`Search`] like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if the query is empty, `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Search<'a>, PexelsError> {
+        validate::not_blank("query", &self.query)?;
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
+
+    /// Converts into a [`SearchBuilder`] that owns its query and color.
+    pub fn into_owned(self) -> SearchBuilder<'static> {
+        SearchBuilder {
+            query: Cow::Owned(self.query.into_owned()),
+            page: self.page,
+            per_page: self.per_page,
+            orientation: self.orientation,
+            size: self.size,
+            color: self.color.map(Color::into_owned),
+            locale: self.locale,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_url() {
+        let next_page =
+            "https://api.pexels.com/v1/search/?color=pink&page=2&per_page=15&query=nature";
+        assert_eq!(
+            Search::from_url(next_page).unwrap().create_uri().unwrap(),
+            "https://api.pexels.com/v1/search?query=nature&page=2&per_page=15&color=pink"
+        );
+
+        let search = SearchBuilder::new()
+            .query("red cars")
+            .orientation(Orientation::Square)
+            .size(Size::Medium)
+            .color(Color::Hex(Hex::from_borrowed_str("#1E90FF").unwrap()))
+            .locale(Locale::pt_BR)
+            .build();
+        let uri = search.create_uri().unwrap();
+        assert_eq!(Search::from_url(&uri).unwrap().create_uri().unwrap(), uri);
+
+        assert!(Search::from_url("https://api.pexels.com/v1/curated?page=2").is_err());
+        assert!(Search::from_url("https://api.pexels.com/v1/search?query=a&color=#12").is_err());
+    }
+
     #[test]
     fn test_query() {
         let uri = SearchBuilder::new().query("bar").build();
@@ -286,6 +466,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_owned_builder_roundtrip() {
+        fn assert_send<T: Send + 'static>(_: &T) {}
+
+        let color = String::from("#1E90FF");
+        let builder = SearchBuilder::new()
+            .query(String::from("mountains"))
+            .color(Color::Hex(Hex::new(color.as_str()).unwrap()))
+            .page(2)
+            .into_owned();
+        drop(color);
+        assert_send(&builder);
+
+        let json = serde_json::to_value(&builder).unwrap();
+        assert_eq!(json["color"], "#1E90FF");
+        let replayed: SearchBuilder<'static> = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            "https://api.pexels.com/v1/search?query=mountains&page=2&color=%231E90FF",
+            replayed.build().create_uri().unwrap()
+        );
+
+        let partial: SearchBuilder = serde_json::from_str(r#"{"color": "Pink"}"#).unwrap();
+        assert_eq!(
+            partial.build().create_uri().unwrap(),
+            "https://api.pexels.com/v1/search?query=&color=pink"
+        );
+        assert!(serde_json::from_str::<Color>("\"#12\"").is_err());
+    }
+
     #[test]
     fn test_locale() {
         let uri = SearchBuilder::new().locale(Locale::sv_SE).build();
@@ -325,4 +534,14 @@ mod tests {
             )))
         );
     }
+
+    #[test]
+    fn test_try_build() {
+        assert_eq!(
+            SearchBuilder::new().try_build().err().map(|e| e.to_string()),
+            Some("Invalid parameter: query must not be empty".to_string())
+        );
+        assert!(SearchBuilder::new().query("cats").per_page(0).try_build().is_err());
+        assert!(SearchBuilder::new().query("cats").page(1).per_page(80).try_build().is_ok());
+    }
 }
@@ -0,0 +1,213 @@
+//! Forward-compatible parsing of responses
+//!
+//! Fields the models don't know are kept in their `extra` map instead of being dropped. In
+//! strict mode the clients turn such fields into [`PexelsError::SchemaDrift`], so tests
+//! notice when the API changes.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::PexelsError;
+
+/// Models that keep the fields they don't know
+pub trait UnknownFields {
+    /// Add the paths of unknown fields below `path` to `fields`
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>);
+
+    /// Paths of all unknown fields, e.g. `photos[0].src.huge`
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        self.collect_unknown_fields("", &mut fields);
+        fields
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Vec<T> {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        for (i, item) in self.iter().enumerate() {
+            item.collect_unknown_fields(&format!("{path}[{i}]"), fields);
+        }
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Option<T> {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        if let Some(value) = self {
+            value.collect_unknown_fields(path, fields);
+        }
+    }
+}
+
+/// Implement [`UnknownFields`] for a struct with an `extra` map and the listed nested models
+macro_rules! unknown_fields {
+    ($ty:ty $(, $field:ident)* $(,)?) => {
+        impl $crate::schema::UnknownFields for $ty {
+            fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+                fields.extend(self.extra.keys().map(|key| $crate::schema::join(path, key)));
+                $(
+                    $crate::schema::UnknownFields::collect_unknown_fields(
+                        &self.$field,
+                        &$crate::schema::join(path, stringify!($field)),
+                        fields,
+                    );
+                )*
+            }
+        }
+    };
+}
+
+pub(crate) use unknown_fields;
+
+/// Path of the field `key` of the object at `path`
+pub(crate) fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Deserialize `null` as the default value, for fields like `liked` that are sometimes null
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Fail with [`PexelsError::SchemaDrift`] in strict mode if `value` has unknown fields
+pub(crate) fn check<T: UnknownFields>(value: T, strict: bool) -> Result<T, PexelsError> {
+    if strict {
+        let fields = value.unknown_fields();
+        if !fields.is_empty() {
+            return Err(PexelsError::SchemaDrift(fields));
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{MediaItem, MediaPage, VideosPage};
+    use crate::{MediaMime, Photo, PhotosResponse, Rendition, VideoFile};
+
+    fn photo() -> serde_json::Value {
+        json!({
+            "id": 1,
+            "width": 10,
+            "height": 20,
+            "url": "https://www.pexels.com/photo/1/",
+            "photographer": "Jane",
+            "photographer_id": 2,
+            "src": {
+                "original": "o", "large2x": "l2", "large": "l", "medium": "m",
+                "small": "s", "portrait": "p", "landscape": "ls", "tiny": "t"
+            }
+        })
+    }
+
+    #[test]
+    fn test_missing_and_null_optional_fields() {
+        let mut value = photo();
+        value["liked"] = json!(null);
+        value["alt"] = json!(null);
+        let photo: Photo = serde_json::from_value(value).unwrap();
+        assert!(!photo.liked);
+        assert_eq!(photo.alt, None);
+        assert_eq!(photo.avg_color, None);
+        assert_eq!(photo.photographer_url, None);
+
+        let file: VideoFile = serde_json::from_value(json!({
+            "id": 3,
+            "file_type": "video/mp4",
+            "link": "https://videos.pexels.com/3.mp4",
+            "fps": null
+        }))
+        .unwrap();
+        assert_eq!(file.fps, None);
+        assert_eq!(file.size, None);
+    }
+
+    #[test]
+    fn test_null_video_file_quality_and_type() {
+        let hls = json!({
+            "id": 4,
+            "quality": null,
+            "file_type": null,
+            "width": null,
+            "height": null,
+            "link": "https://player.vimeo.com/external/4.m3u8"
+        });
+        let page: VideosPage = serde_json::from_value(json!({
+            "page": 1,
+            "per_page": 1,
+            "total_results": 1,
+            "videos": [{
+                "id": 5, "width": 1920, "height": 1080, "url": "https://www.pexels.com/video/5/",
+                "image": "https://images.pexels.com/5.jpeg", "duration": 7,
+                "user": {"id": 6, "name": "Jane", "url": "https://www.pexels.com/@jane"},
+                "video_files": [hls.clone()], "video_pictures": []
+            }]
+        }))
+        .unwrap();
+        let file = &page.videos[0].video_files[0];
+        assert_eq!(file.quality, None);
+        assert_eq!(file.file_type, MediaMime::default());
+        assert_eq!(file.quality(), None);
+
+        let file: VideoFile = serde_json::from_value(hls).unwrap();
+        assert_eq!(file.quality, None);
+        assert_eq!(file.file_type.format(), None);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_kept() {
+        let mut value = photo();
+        value["src"]["huge"] = json!("h");
+        value["blurhash"] = json!("abc");
+        let page = json!({
+            "page": 1,
+            "per_page": 1,
+            "total_results": 1,
+            "photos": [value],
+            "trace_id": "x"
+        });
+
+        let response: PhotosResponse = serde_json::from_value(page.clone()).unwrap();
+        assert_eq!(response.photos[0].extra["blurhash"], "abc");
+        assert_eq!(
+            response.unknown_fields(),
+            ["trace_id", "photos[0].blurhash", "photos[0].src.huge"]
+        );
+        assert_eq!(serde_json::to_value(&response).unwrap()["photos"][0]["blurhash"], "abc");
+
+        assert!(check(response.clone(), false).is_ok());
+        assert_eq!(
+            check(response, true).unwrap_err(),
+            PexelsError::SchemaDrift(vec![
+                "trace_id".to_string(),
+                "photos[0].blurhash".to_string(),
+                "photos[0].src.huge".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_media_type_tag_is_not_unknown() {
+        let mut value = photo();
+        value["type"] = json!("Photo");
+        let page: MediaPage = serde_json::from_value(json!({
+            "id": "abc",
+            "media": [value],
+            "page": 1,
+            "per_page": 1,
+            "total_results": 1
+        }))
+        .unwrap();
+        assert!(matches!(page.media[0], MediaItem::Photo(_)));
+        assert!(page.unknown_fields().is_empty());
+    }
+}
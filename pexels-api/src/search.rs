@@ -1,13 +1,17 @@
-use crate::{MediaSort, MediaType, Orientation, Size};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+use crate::page_url::{Endpoint, PageUrl};
+use crate::{CollectionId, Color, Locale, MediaSort, MediaType, Orientation, PexelsError, Size};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchParams {
     pub page: Option<u32>,
     pub per_page: Option<u32>,
     pub orientation: Option<Orientation>,
     pub size: Option<Size>,
-    pub color: Option<String>,
-    pub locale: Option<String>,
+    pub color: Option<Color<'static>>,
+    pub locale: Option<Locale>,
 }
 
 impl SearchParams {
@@ -15,6 +19,20 @@ impl SearchParams {
         Self::default()
     }
 
+    /// Parse a photo search URL, e.g. a `next_page` link, into its query and parameters
+    pub fn from_url(url: &str) -> Result<(String, Self), PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::PhotoSearch)?;
+        let params = Self {
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            orientation: url.get("orientation")?,
+            size: url.get("size")?,
+            color: url.get("color")?,
+            locale: url.get("locale")?,
+        };
+        Ok((url.search_query()?, params))
+    }
+
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
@@ -35,13 +53,13 @@ impl SearchParams {
         self
     }
 
-    pub fn color(mut self, color: impl Into<String>) -> Self {
+    pub fn color(mut self, color: impl Into<Color<'static>>) -> Self {
         self.color = Some(color.into());
         self
     }
 
-    pub fn locale(mut self, locale: impl Into<String>) -> Self {
-        self.locale = Some(locale.into());
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
         self
     }
 
@@ -65,11 +83,11 @@ impl SearchParams {
         }
 
         if let Some(color) = &self.color {
-            params.push(("color".to_string(), color.clone()));
+            params.push(("color".to_string(), color.as_str().to_string()));
         }
 
         if let Some(locale) = &self.locale {
-            params.push(("locale".to_string(), locale.clone()));
+            params.push(("locale".to_string(), locale.as_str().to_string()));
         }
 
         params
@@ -92,6 +110,12 @@ impl PaginationParams {
         Self::default()
     }
 
+    /// Parse the page and page size of any URL that returns pages, e.g. a `next_page` link
+    pub fn from_url(url: &str) -> Result<Self, PexelsError> {
+        let url = PageUrl::parse(url)?;
+        Ok(Self { page: url.get("page")?, per_page: url.get("per_page")? })
+    }
+
     /// Set the page number
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
@@ -120,7 +144,8 @@ impl PaginationParams {
 }
 
 /// Video search parameters
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VideoSearchParams {
     /// Page number to retrieve
     pub page: Option<u32>,
@@ -129,13 +154,13 @@ pub struct VideoSearchParams {
     pub per_page: Option<u32>,
 
     /// Orientation filter (landscape, portrait, square)
-    pub orientation: Option<String>,
+    pub orientation: Option<Orientation>,
 
     /// Size filter (large, medium, small)
-    pub size: Option<String>,
+    pub size: Option<Size>,
 
     /// Locale for localized results
-    pub locale: Option<String>,
+    pub locale: Option<Locale>,
 }
 
 impl VideoSearchParams {
@@ -144,6 +169,19 @@ impl VideoSearchParams {
         Self::default()
     }
 
+    /// Parse a video search URL, e.g. a `next_page` link, into its query and parameters
+    pub fn from_url(url: &str) -> Result<(String, Self), PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::VideoSearch)?;
+        let params = Self {
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            orientation: url.get("orientation")?,
+            size: url.get("size")?,
+            locale: url.get("locale")?,
+        };
+        Ok((url.search_query()?, params))
+    }
+
     /// Set the page number
     pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
@@ -157,20 +195,20 @@ impl VideoSearchParams {
     }
 
     /// Set the orientation filter
-    pub fn orientation(mut self, orientation: impl Into<String>) -> Self {
-        self.orientation = Some(orientation.into());
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
         self
     }
 
     /// Set the size filter
-    pub fn size(mut self, size: impl Into<String>) -> Self {
-        self.size = Some(size.into());
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = Some(size);
         self
     }
 
     /// Set the locale for localized results
-    pub fn locale(mut self, locale: impl Into<String>) -> Self {
-        self.locale = Some(locale.into());
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
         self
     }
 
@@ -186,15 +224,15 @@ impl VideoSearchParams {
         }
 
         if let Some(orientation) = &self.orientation {
-            params.push(("orientation".to_string(), orientation.clone()));
+            params.push(("orientation".to_string(), orientation.to_string()));
         }
 
         if let Some(size) = &self.size {
-            params.push(("size".to_string(), size.clone()));
+            params.push(("size".to_string(), size.to_string()));
         }
 
         if let Some(locale) = &self.locale {
-            params.push(("locale".to_string(), locale.clone()));
+            params.push(("locale".to_string(), locale.as_str().to_string()));
         }
 
         params
@@ -229,6 +267,19 @@ impl PopularVideoParams {
         Self::default()
     }
 
+    /// Parse a popular videos URL, e.g. a `next_page` link
+    pub fn from_url(url: &str) -> Result<Self, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::PopularVideos)?;
+        Ok(Self {
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            min_width: url.get("min_width")?,
+            min_height: url.get("min_height")?,
+            min_duration: url.get("min_duration")?,
+            max_duration: url.get("max_duration")?,
+        })
+    }
+
     /// Create popular video params from simple pagination params.
     pub fn from_pagination(params: &PaginationParams) -> Self {
         Self { page: params.page, per_page: params.per_page, ..Self::default() }
@@ -323,6 +374,19 @@ impl CollectionMediaParams {
         Self::default()
     }
 
+    /// Parse a collection media URL, e.g. a `next_page` link, into the collection id and
+    /// parameters
+    pub fn from_url(url: &str) -> Result<(CollectionId, Self), PexelsError> {
+        let url = PageUrl::parse(url)?;
+        let params = Self {
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            media_type: url.get("type")?,
+            sort: url.get("sort")?,
+        };
+        Ok((url.collection_id()?, params))
+    }
+
     /// Create collection media params from simple pagination params.
     pub fn from_pagination(params: &PaginationParams) -> Self {
         Self { page: params.page, per_page: params.per_page, ..Self::default() }
@@ -424,4 +488,89 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn search_params_are_typed() {
+        let params = SearchParams::new()
+            .orientation("portrait".parse().unwrap())
+            .color(crate::RgbColor::new(0xFF, 0x88, 0x00))
+            .locale(Locale::fr_FR)
+            .to_query_params();
+        assert_eq!(
+            params,
+            vec![
+                ("orientation".to_string(), "portrait".to_string()),
+                ("color".to_string(), "#FF8800".to_string()),
+                ("locale".to_string(), "fr-FR".to_string()),
+            ]
+        );
+        assert!("portriat".parse::<Orientation>().is_err());
+    }
+
+    #[test]
+    fn params_from_next_page_urls() {
+        let (query, params) = SearchParams::from_url(
+            "https://api.pexels.com/v1/search/?color=%23FF8800&locale=fr-FR&page=2&per_page=15&query=red+cars",
+        )
+        .unwrap();
+        assert_eq!(query, "red cars");
+        assert_eq!(params.page, Some(2));
+        assert_eq!(params.per_page, Some(15));
+        assert_eq!(params.color, Some("#FF8800".parse().unwrap()));
+        assert_eq!(params.locale, Some(Locale::fr_FR));
+
+        let (query, params) = VideoSearchParams::from_url(
+            "https://api.pexels.com/videos/search/?orientation=portrait&page=3&query=waves",
+        )
+        .unwrap();
+        assert_eq!(query, "waves");
+        assert_eq!(params.orientation, Some(Orientation::Portrait));
+        assert_eq!(params.page, Some(3));
+
+        let params = PopularVideoParams::from_url(
+            "https://api.pexels.com/videos/popular?min_duration=5&page=2",
+        )
+        .unwrap();
+        assert_eq!(
+            params.to_query_params(),
+            PopularVideoParams::new().page(2).min_duration(5).to_query_params()
+        );
+
+        let (id, params) = CollectionMediaParams::from_url(
+            "https://api.pexels.com/v1/collections/8xntbhr/?page=2&per_page=10&sort=desc&type=videos",
+        )
+        .unwrap();
+        assert_eq!(id, CollectionId::new("8xntbhr"));
+        assert_eq!(params.media_type, Some(MediaType::Video));
+        assert_eq!(params.sort, Some(MediaSort::Desc));
+
+        let params =
+            PaginationParams::from_url("https://api.pexels.com/v1/curated/?page=4&per_page=20")
+                .unwrap();
+        assert_eq!((params.page, params.per_page), (Some(4), Some(20)));
+
+        assert!(matches!(
+            SearchParams::from_url("https://api.pexels.com/videos/search?query=waves"),
+            Err(PexelsError::InvalidPageUrl(_))
+        ));
+        assert!(SearchParams::from_url("https://api.pexels.com/v1/search?page=2").is_err());
+        assert!(VideoSearchParams::from_url(
+            "https://api.pexels.com/videos/search?query=waves&size=huge"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn video_search_params_serde() {
+        let params: VideoSearchParams = serde_json::from_str(
+            r#"{"orientation": "square", "size": "large", "locale": "ja-JP"}"#,
+        )
+        .unwrap();
+        assert_eq!(params.orientation, Some(Orientation::Square));
+        assert_eq!(params.size, Some(Size::Large));
+        assert_eq!(params.locale, Some(Locale::ja_JP));
+
+        let typo = serde_json::from_str::<VideoSearchParams>(r#"{"orientation": "portriat"}"#);
+        assert!(typo.is_err());
+    }
 }
@@ -0,0 +1,91 @@
+//! Minimal HTTP server for exercising the clients and downloads without network access
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::DownloadManager;
+
+pub(crate) const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR0123456789abcdef";
+
+/// Serve the given raw responses, one per connection, and return the URL of `photo.png`
+pub(crate) async fn serve(responses: Vec<Vec<u8>>) -> String {
+    serve_logged(responses).await.0
+}
+
+/// Like [`serve`], and also return the request lines received, e.g. `HEAD /photo.png HTTP/1.1`
+pub(crate) async fn serve_logged(responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let (base_url, log) = listen(responses).await;
+    (format!("{base_url}/photo.png"), log)
+}
+
+/// Like [`serve`], but return the base URL of the server, e.g. `http://127.0.0.1:4321`
+pub(crate) async fn serve_base_url(responses: Vec<Vec<u8>>) -> String {
+    listen(responses).await.0
+}
+
+async fn listen(responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let requests = Arc::clone(&log);
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let line = String::from_utf8_lossy(&request).lines().next().unwrap_or_default().into();
+            requests.lock().unwrap().push(line);
+            socket.write_all(&response).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+    (format!("http://{addr}"), log)
+}
+
+/// A raw `image/png` response
+pub(crate) fn response(status: &str, body: &[u8]) -> Vec<u8> {
+    raw_response(status, "image/png", body)
+}
+
+/// A raw `200 OK` JSON response
+pub(crate) fn json_response(body: &serde_json::Value) -> Vec<u8> {
+    raw_response("200 OK", "application/json", body.to_string().as_bytes())
+}
+
+/// A `416 Range Not Satisfiable` response for a resource of `total` bytes
+pub(crate) fn range_not_satisfiable(total: u64) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{total}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+    )
+    .into_bytes()
+}
+
+fn raw_response(status: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+/// A manager that never goes through a proxy
+pub(crate) fn manager() -> DownloadManager {
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    DownloadManager::with_client(client, 1)
+}
+
+/// A fresh, empty directory below the system temp directory
+pub(crate) fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("pexels-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
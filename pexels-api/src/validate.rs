@@ -0,0 +1,81 @@
+//! Checks of request parameters against the constraints of the Pexels API
+//!
+//! Each check fails with [`PexelsError::InvalidParameter`] whose message starts with the
+//! name of the offending field.
+
+use crate::PexelsError;
+
+/// Largest page size the API accepts
+pub(crate) const MAX_PER_PAGE: usize = 80;
+
+/// `page` must be at least 1
+pub(crate) fn page(page: Option<usize>) -> Result<(), PexelsError> {
+    match page {
+        Some(0) => Err(PexelsError::InvalidParameter("page must be at least 1, got 0".to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// `per_page` must be between 1 and [`MAX_PER_PAGE`]
+pub(crate) fn per_page(per_page: Option<usize>) -> Result<(), PexelsError> {
+    match per_page {
+        Some(n) if !(1..=MAX_PER_PAGE).contains(&n) => Err(PexelsError::InvalidParameter(format!(
+            "per_page must be between 1 and {MAX_PER_PAGE}, got {n}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Strings such as queries and collection ids must not be blank
+pub(crate) fn not_blank(field: &str, value: &str) -> Result<(), PexelsError> {
+    if value.trim().is_empty() {
+        return Err(PexelsError::InvalidParameter(format!("{field} must not be empty")));
+    }
+    Ok(())
+}
+
+/// Numeric ids start at 1
+pub(crate) fn id(id: u64) -> Result<(), PexelsError> {
+    if id == 0 {
+        return Err(PexelsError::InvalidParameter("id must not be 0".to_string()));
+    }
+    Ok(())
+}
+
+/// The lower bound `min` of a range must not exceed its upper bound `max`
+pub(crate) fn range(
+    (min_field, min): (&str, Option<usize>),
+    (max_field, max): (&str, Option<usize>),
+) -> Result<(), PexelsError> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(PexelsError::InvalidParameter(format!(
+            "{min_field} ({min}) must not exceed {max_field} ({max})"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks() {
+        assert!(page(Some(1)).is_ok() && page(None).is_ok());
+        assert_eq!(
+            page(Some(0)),
+            Err(PexelsError::InvalidParameter("page must be at least 1, got 0".to_string()))
+        );
+        assert!(per_page(Some(80)).is_ok());
+        assert!(per_page(Some(0)).is_err() && per_page(Some(81)).is_err());
+        assert!(not_blank("query", " ").is_err());
+        assert!(id(0).is_err());
+        assert!(range(("min_duration", Some(5)), ("max_duration", Some(5))).is_ok());
+        assert_eq!(
+            range(("min_duration", Some(30)), ("max_duration", Some(10))),
+            Err(PexelsError::InvalidParameter(
+                "min_duration (30) must not exceed max_duration (10)".to_string()
+            ))
+        );
+    }
+}
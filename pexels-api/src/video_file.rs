@@ -0,0 +1,205 @@
+//! Typed quality labels and MIME types of video files
+//!
+//! The API isn't consistent about case, e.g. `hd` and `HD` both occur, so known values are
+//! parsed case-insensitively and anything else is kept as it was sent.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MediaFormat;
+
+/// Quality label of a video file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum VideoFileQuality {
+    /// High definition, e.g. 1280x720 or 1920x1080
+    Hd,
+    /// Standard definition, e.g. 640x360
+    Sd,
+    /// Ultra high definition, e.g. 3840x2160
+    Uhd,
+    /// HTTP live stream playlist
+    Hls,
+    /// Any other label, as sent by the API
+    Other(String),
+}
+
+impl VideoFileQuality {
+    /// The label as the API uses it, e.g. `hd`
+    pub fn as_str(&self) -> &str {
+        match self {
+            VideoFileQuality::Hd => "hd",
+            VideoFileQuality::Sd => "sd",
+            VideoFileQuality::Uhd => "uhd",
+            VideoFileQuality::Hls => "hls",
+            VideoFileQuality::Other(label) => label,
+        }
+    }
+}
+
+impl From<&str> for VideoFileQuality {
+    fn from(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "hd" => VideoFileQuality::Hd,
+            "sd" => VideoFileQuality::Sd,
+            "uhd" => VideoFileQuality::Uhd,
+            "hls" => VideoFileQuality::Hls,
+            _ => VideoFileQuality::Other(label.to_string()),
+        }
+    }
+}
+
+impl From<String> for VideoFileQuality {
+    fn from(label: String) -> Self {
+        match VideoFileQuality::from(label.as_str()) {
+            VideoFileQuality::Other(_) => VideoFileQuality::Other(label),
+            quality => quality,
+        }
+    }
+}
+
+impl From<VideoFileQuality> for String {
+    fn from(quality: VideoFileQuality) -> Self {
+        match quality {
+            VideoFileQuality::Other(label) => label,
+            quality => quality.as_str().to_string(),
+        }
+    }
+}
+
+impl Display for VideoFileQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VideoFileQuality {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+/// MIME type of a video file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum MediaMime {
+    /// `video/mp4`
+    Mp4,
+    /// `video/webm`
+    Webm,
+    /// `video/quicktime`
+    QuickTime,
+    /// `application/x-mpegurl`, an HLS playlist
+    Hls,
+    /// Any other type, as sent by the API
+    Other(String),
+}
+
+/// An empty type, for files the API sends without one
+impl Default for MediaMime {
+    fn default() -> Self {
+        MediaMime::Other(String::new())
+    }
+}
+
+impl MediaMime {
+    /// The MIME type, e.g. `video/mp4`
+    pub fn as_str(&self) -> &str {
+        match self {
+            MediaMime::Mp4 => "video/mp4",
+            MediaMime::Webm => "video/webm",
+            MediaMime::QuickTime => "video/quicktime",
+            MediaMime::Hls => "application/x-mpegurl",
+            MediaMime::Other(mime) => mime,
+        }
+    }
+
+    /// The file format, if it is one the downloader recognises
+    pub fn format(&self) -> Option<MediaFormat> {
+        MediaFormat::from_mime_type(self.as_str())
+    }
+}
+
+impl From<&str> for MediaMime {
+    fn from(mime: &str) -> Self {
+        let essence = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "video/mp4" => MediaMime::Mp4,
+            "video/webm" => MediaMime::Webm,
+            "video/quicktime" => MediaMime::QuickTime,
+            "application/x-mpegurl" | "application/vnd.apple.mpegurl" => MediaMime::Hls,
+            _ => MediaMime::Other(mime.to_string()),
+        }
+    }
+}
+
+impl From<String> for MediaMime {
+    fn from(mime: String) -> Self {
+        match MediaMime::from(mime.as_str()) {
+            MediaMime::Other(_) => MediaMime::Other(mime),
+            known => known,
+        }
+    }
+}
+
+impl From<MediaMime> for String {
+    fn from(mime: MediaMime) -> Self {
+        match mime {
+            MediaMime::Other(mime) => mime,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl Display for MediaMime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MediaMime {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_is_case_insensitive() {
+        assert_eq!(VideoFileQuality::from("HD"), VideoFileQuality::Hd);
+        assert_eq!(" uhd".parse::<VideoFileQuality>().unwrap(), VideoFileQuality::Uhd);
+        assert_eq!(VideoFileQuality::from("8k"), VideoFileQuality::Other("8k".to_string()));
+        assert_eq!(VideoFileQuality::Sd.to_string(), "sd");
+    }
+
+    #[test]
+    fn test_quality_serde() {
+        let quality: VideoFileQuality = serde_json::from_str("\"HLS\"").unwrap();
+        assert_eq!(quality, VideoFileQuality::Hls);
+        assert_eq!(serde_json::to_string(&quality).unwrap(), "\"hls\"");
+
+        let quality: VideoFileQuality = serde_json::from_str("\"Cinema\"").unwrap();
+        assert_eq!(serde_json::to_string(&quality).unwrap(), "\"Cinema\"");
+    }
+
+    #[test]
+    fn test_mime() {
+        assert_eq!(MediaMime::from("Video/MP4; codecs=avc1"), MediaMime::Mp4);
+        assert_eq!(MediaMime::from("application/vnd.apple.mpegurl"), MediaMime::Hls);
+        assert_eq!(MediaMime::QuickTime.format(), Some(MediaFormat::Mov));
+        assert_eq!(MediaMime::Hls.format(), None);
+
+        let mime: MediaMime = serde_json::from_str("\"video/x-matroska\"").unwrap();
+        assert_eq!(mime, MediaMime::Other("video/x-matroska".to_string()));
+        assert_eq!(serde_json::to_string(&MediaMime::Webm).unwrap(), "\"video/webm\"");
+    }
+}
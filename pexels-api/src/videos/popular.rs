@@ -1,4 +1,5 @@
-use crate::{Pexels, PexelsError, VideoResponse, PEXELS_API, PEXELS_VIDEO_PATH};
+use crate::page_url::{Endpoint, PageUrl};
+use crate::{validate, Pexels, PexelsError, VideoResponse, PEXELS_API, PEXELS_VIDEO_PATH};
 use url::Url;
 
 /// Path to get popular videos.
@@ -20,6 +21,20 @@ impl Popular {
         PopularBuilder::default()
     }
 
+    /// Parses a popular videos URL, such as the `next_page` of a [`VideoResponse`], back into
+    /// a [`Popular`].
+    pub fn from_url(url: &str) -> Result<Popular, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::PopularVideos)?;
+        Ok(Popular {
+            min_width: url.get("min_width")?,
+            min_height: url.get("min_height")?,
+            min_duration: url.get("min_duration")?,
+            max_duration: url.get("max_duration")?,
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+        })
+    }
+
     /// Creates a URI from the provided parameters.
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!("{PEXELS_API}/{PEXELS_VIDEO_PATH}/{PEXELS_POPULAR_PATH}");
@@ -57,7 +72,7 @@ impl Popular {
     pub async fn fetch(&self, client: &Pexels) -> Result<VideoResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let response_video: VideoResponse = serde_json::from_value(response)?;
+        let response_video: VideoResponse = client.parse(response)?;
         Ok(response_video)
     }
 }
@@ -126,12 +141,31 @@ impl PopularBuilder {
             max_duration: self.max_duration,
         }
     }
+
+    /// Builds `Popular` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if `page` is 0, `per_page` is not between 1 and 80 or `min_duration` exceeds `max_duration`.
+    pub fn try_build(self) -> Result<Popular, PexelsError> {
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        validate::range(("min_duration", self.min_duration), ("max_duration", self.max_duration))?;
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_url() {
+        let uri = PopularBuilder::new().min_width(1920).max_duration(30).page(2).build();
+        let uri = uri.create_uri().unwrap();
+        assert_eq!(Popular::from_url(&uri).unwrap().create_uri().unwrap(), uri);
+        assert!(Popular::from_url("https://api.pexels.com/videos/search?query=a").is_err());
+    }
+
     #[test]
     fn test_min_width() {
         let uri = PopularBuilder::new().min_width(1).build();
@@ -173,4 +207,21 @@ mod tests {
         let uri = PopularBuilder::new().per_page(1).build();
         assert_eq!("https://api.pexels.com/videos/popular?per_page=1", uri.create_uri().unwrap());
     }
+
+    #[test]
+    fn test_try_build() {
+        assert!(PopularBuilder::new().min_duration(5).max_duration(30).try_build().is_ok());
+        assert_eq!(
+            PopularBuilder::new().min_duration(30).max_duration(10).try_build().err(),
+            Some(PexelsError::InvalidParameter(
+                "min_duration (30) must not exceed max_duration (10)".to_string()
+            ))
+        );
+        assert_eq!(
+            PopularBuilder::new().per_page(500).try_build().err(),
+            Some(PexelsError::InvalidParameter(
+                "per_page must be between 1 and 80, got 500".to_string()
+            ))
+        );
+    }
 }
@@ -1,14 +1,21 @@
+use crate::page_url::{Endpoint, PageUrl};
 use crate::{
-    Locale, Orientation, Pexels, PexelsError, Size, VideoResponse, PEXELS_API, PEXELS_VIDEO_PATH,
+    validate, Locale, Orientation, Pexels, PexelsError, Size, VideoResponse, PEXELS_API,
+    PEXELS_VIDEO_PATH,
 };
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use url::Url;
 
 /// The path for the search endpoint.
 const PEXELS_VIDEO_SEARCH_PATH: &str = "search";
 
 /// Represents a search request to the Pexels API for videos.
+///
+/// Like [`SearchBuilder`] it can be serialized, e.g. to persist a search and replay it later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Search<'a> {
-    query: &'a str,
+    query: Cow<'a, str>,
     page: Option<usize>,
     per_page: Option<usize>,
     orientation: Option<Orientation>,
@@ -22,13 +29,27 @@ impl<'a> Search<'a> {
         SearchBuilder::default()
     }
 
+    /// Parses a video search URL, such as the `next_page` of a [`VideoResponse`], back into a
+    /// [`Search`].
+    pub fn from_url(url: &str) -> Result<Search<'static>, PexelsError> {
+        let url = PageUrl::expect(url, Endpoint::VideoSearch)?;
+        Ok(Search {
+            query: Cow::Owned(url.search_query()?),
+            page: url.get("page")?,
+            per_page: url.get("per_page")?,
+            orientation: url.get("orientation")?,
+            size: url.get("size")?,
+            locale: url.get("locale")?,
+        })
+    }
+
     /// Creates a URI from the provided parameters.
     pub fn create_uri(&self) -> crate::BuilderResult {
         let uri = format!("{PEXELS_API}/{PEXELS_VIDEO_PATH}/{PEXELS_VIDEO_SEARCH_PATH}");
 
         let mut url = Url::parse(uri.as_str())?;
 
-        url.query_pairs_mut().append_pair("query", self.query);
+        url.query_pairs_mut().append_pair("query", &self.query);
 
         if let Some(page) = &self.page {
             url.query_pairs_mut().append_pair("page", page.to_string().as_str());
@@ -57,15 +78,19 @@ impl<'a> Search<'a> {
     pub async fn fetch(&self, client: &Pexels) -> Result<VideoResponse, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let response_video: VideoResponse = serde_json::from_value(response)?;
+        let response_video: VideoResponse = client.parse(response)?;
         Ok(response_video)
     }
 }
 
 /// Builder for [`Search`].
-#[derive(Default)]
+///
+/// The query may be borrowed or owned; a `SearchBuilder<'static>` is `Send` and can be
+/// stored, serialized and deserialized.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchBuilder<'a> {
-    query: &'a str,
+    query: Cow<'a, str>,
     page: Option<usize>,
     per_page: Option<usize>,
     orientation: Option<Orientation>,
@@ -76,12 +101,19 @@ pub struct SearchBuilder<'a> {
 impl<'a> SearchBuilder<'a> {
     /// Create a new [`SearchBuilder`].
     pub fn new() -> Self {
-        Self { query: "", page: None, per_page: None, orientation: None, size: None, locale: None }
+        Self {
+            query: Cow::Borrowed(""),
+            page: None,
+            per_page: None,
+            orientation: None,
+            size: None,
+            locale: None,
+        }
     }
 
     /// Sets the search query.
-    pub fn query(mut self, query: &'a str) -> Self {
-        self.query = query;
+    pub fn query(mut self, query: impl Into<Cow<'a, str>>) -> Self {
+        self.query = query.into();
         self
     }
 
@@ -126,6 +158,29 @@ impl<'a> SearchBuilder<'a> {
             locale: self.locale,
         }
     }
+
+    /// Builds `Search` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if the query is empty, `page` is 0 or `per_page` is not between 1 and 80.
+    pub fn try_build(self) -> Result<Search<'a>, PexelsError> {
+        validate::not_blank("query", &self.query)?;
+        validate::page(self.page)?;
+        validate::per_page(self.per_page)?;
+        Ok(self.build())
+    }
+
+    /// Converts into a [`SearchBuilder`] that owns its query.
+    pub fn into_owned(self) -> SearchBuilder<'static> {
+        SearchBuilder {
+            query: Cow::Owned(self.query.into_owned()),
+            page: self.page,
+            per_page: self.per_page,
+            orientation: self.orientation,
+            size: self.size,
+            locale: self.locale,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -133,6 +188,20 @@ mod tests {
     use crate::videos::search::SearchBuilder;
     use crate::{Locale, Orientation, Size};
 
+    #[test]
+    fn test_from_url() {
+        use crate::videos::search::Search;
+
+        let search = Search::from_url(
+            "https://api.pexels.com/videos/search/?locale=de-DE&page=3&query=ocean+waves&size=large",
+        );
+        assert_eq!(
+            search.unwrap().create_uri().unwrap(),
+            "https://api.pexels.com/videos/search?query=ocean+waves&page=3&size=large&locale=de-DE"
+        );
+        assert!(Search::from_url("https://api.pexels.com/v1/search?query=waves").is_err());
+    }
+
     #[test]
     fn test_query() {
         let uri = SearchBuilder::new().query("bar").build();
@@ -172,6 +241,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_owned_builder_roundtrip() {
+        fn assert_send<T: Send + 'static>(_: &T) {}
+
+        let builder = SearchBuilder::new()
+            .query(String::from("ocean waves"))
+            .orientation(Orientation::Portrait)
+            .size(Size::Medium)
+            .locale(Locale::de_DE);
+        assert_send(&builder);
+
+        let json = serde_json::to_value(&builder).unwrap();
+        assert_eq!(json["query"], "ocean waves");
+        assert_eq!(json["orientation"], "portrait");
+        assert_eq!(json["locale"], "de-DE");
+        let replayed: SearchBuilder<'static> = serde_json::from_value(json).unwrap();
+        assert_eq!(replayed.build().create_uri(), builder.clone().build().create_uri());
+
+        let query = String::from("borrowed");
+        let owned = SearchBuilder::new().query(query.as_str()).into_owned();
+        drop(query);
+        assert_send(&owned);
+    }
+
     #[test]
     fn test_locale() {
         let uri = SearchBuilder::new().locale(Locale::sv_SE).build();
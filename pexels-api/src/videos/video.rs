@@ -1,11 +1,11 @@
-use crate::{Pexels, PexelsError, Video, PEXELS_API, PEXELS_VIDEO_PATH};
+use crate::{validate, Pexels, PexelsError, Video, VideoId, PEXELS_API, PEXELS_VIDEO_PATH};
 use url::Url;
 /// Path to get a specific video.
 const PEXELS_GET_VIDEO_PATH: &str = "videos";
 
 /// Represents a request to fetch a specific video by its ID from the Pexels API.
 pub struct FetchVideo {
-    id: usize,
+    id: VideoId,
 }
 
 impl FetchVideo {
@@ -28,7 +28,7 @@ impl FetchVideo {
     pub async fn fetch(&self, client: &Pexels) -> Result<Video, PexelsError> {
         let url = self.create_uri()?;
         let response = client.make_request(url.as_str()).await?;
-        let video: Video = serde_json::from_value(response)?;
+        let video: Video = client.parse(response)?;
         Ok(video)
     }
 }
@@ -36,18 +36,18 @@ impl FetchVideo {
 /// Builder for `FetchVideo`.
 #[derive(Default)]
 pub struct FetchVideoBuilder {
-    id: usize,
+    id: VideoId,
 }
 
 impl FetchVideoBuilder {
     /// Creates a new `FetchVideoBuilder`.
     pub fn new() -> Self {
-        Self { id: 0 }
+        Self { id: VideoId::default() }
     }
 
     /// Sets the ID of the video to be fetched.
-    pub fn id(mut self, id: usize) -> Self {
-        self.id = id;
+    pub fn id(mut self, id: impl Into<VideoId>) -> Self {
+        self.id = id.into();
         self
     }
 
@@ -55,6 +55,15 @@ impl FetchVideoBuilder {
     pub fn build(self) -> FetchVideo {
         FetchVideo { id: self.id }
     }
+
+    /// Builds `FetchVideo` like `build`, but checks the parameters against the API constraints first.
+    ///
+    /// # Errors
+    /// Returns [`PexelsError::InvalidParameter`] naming the field if the id is 0.
+    pub fn try_build(self) -> Result<FetchVideo, PexelsError> {
+        validate::id(self.id.get())?;
+        Ok(self.build())
+    }
 }
 
 #[cfg(test)]
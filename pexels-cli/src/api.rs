@@ -1,6 +1,7 @@
 use pexels_api::{
     CollectionsResponse, MediaBuilder, MediaResponse, MediaSort, MediaType, Pexels, PexelsError,
-    Photo, PhotosResponse, SearchBuilder, Video, VideoResponse, VideoSearchBuilder,
+    Photo, PhotoId, PhotosResponse, SearchBuilder, Video, VideoId, VideoResponse,
+    VideoSearchBuilder,
 };
 use std::env;
 
@@ -28,14 +29,14 @@ pub async fn search_videos(
     Ok(videos)
 }
 
-pub async fn get_photo(id: usize) -> Result<Photo, PexelsError> {
+pub async fn get_photo(id: PhotoId) -> Result<Photo, PexelsError> {
     let api_key = env::var("PEXELS_API_KEY")?;
     let client = Pexels::new(api_key);
     let photo = client.get_photo(id).await?;
     Ok(photo)
 }
 
-pub async fn get_video(id: usize) -> Result<Video, PexelsError> {
+pub async fn get_video(id: VideoId) -> Result<Video, PexelsError> {
     let api_key = env::var("PEXELS_API_KEY")?;
     let client = Pexels::new(api_key);
     let video = client.get_video(id).await?;
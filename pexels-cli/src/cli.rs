@@ -1,4 +1,6 @@
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use clap::{Parser, Subcommand};
+use pexels_api::{MediaSort, MediaType, PhotoId, VideoId};
 
 #[derive(Parser, Debug)]
 #[clap(name = "pexels-cli", version = "0.0.1", about = "A CLI for interacting with the Pexels API")]
@@ -13,7 +15,7 @@ pub enum Command {
     SearchPhotos {
         #[clap(short, long)]
         query: String,
-        #[clap(short, long, default_value = "15")]
+        #[clap(short = 'n', long, default_value = "15")]
         per_page: usize,
         #[clap(short, long, default_value = "1")]
         page: usize,
@@ -22,7 +24,7 @@ pub enum Command {
     SearchVideos {
         #[clap(short, long)]
         query: String,
-        #[clap(short, long, default_value = "15")]
+        #[clap(short = 'n', long, default_value = "15")]
         per_page: usize,
         #[clap(short, long, default_value = "1")]
         page: usize,
@@ -30,16 +32,16 @@ pub enum Command {
     /// Get a specific photo by ID
     GetPhoto {
         #[clap(short, long)]
-        id: usize,
+        id: PhotoId,
     },
     /// Get a specific video by ID
     GetVideo {
         #[clap(short, long)]
-        id: usize,
+        id: VideoId,
     },
     /// Search for collections
     SearchCollections {
-        #[clap(short, long, default_value = "15")]
+        #[clap(short = 'n', long, default_value = "15")]
         per_page: usize,
         #[clap(short, long, default_value = "1")]
         page: usize,
@@ -48,13 +50,47 @@ pub enum Command {
     SearchMedia {
         #[clap(short, long)]
         query: String,
-        #[clap(short, long, default_value = "15")]
+        #[clap(short = 'n', long, default_value = "15")]
         per_page: usize,
         #[clap(short, long, default_value = "1")]
         page: usize,
-        #[clap(short, long, default_value = "")]
-        r#type: String,
-        #[clap(short, long, default_value = "asc")]
-        sort: String,
+        /// Only return media of this type, all types when omitted
+        #[clap(short, long, value_parser = media_types())]
+        r#type: Option<MediaType>,
+        #[clap(short, long, default_value = "asc", value_parser = media_sorts())]
+        sort: MediaSort,
     },
 }
+
+/// Accept the values of `MediaType::ALL` and list them in `--help`
+fn media_types() -> impl TypedValueParser<Value = MediaType> {
+    PossibleValuesParser::new(MediaType::ALL.iter().map(MediaType::as_str))
+        .map(|value| value.parse().expect("possible values parse"))
+}
+
+/// Accept the values of `MediaSort::ALL` and list them in `--help`
+fn media_sorts() -> impl TypedValueParser<Value = MediaSort> {
+    PossibleValuesParser::new(MediaSort::ALL.iter().map(MediaSort::as_str))
+        .map(|value| value.parse().expect("possible values parse"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+        let cli = Cli::parse_from(["pexels-cli", "search-media", "-q", "cats", "-t", "videos"]);
+        assert!(matches!(
+            cli.command,
+            Command::SearchMedia { r#type: Some(MediaType::Video), sort: MediaSort::Asc, .. }
+        ));
+        assert!(Cli::try_parse_from(["pexels-cli", "search-media", "-q", "x", "-t", ""]).is_err());
+
+        let help = Cli::command().find_subcommand_mut("search-media").unwrap().render_help();
+        assert!(help.to_string().contains("[possible values: photos, videos]"));
+        assert!(help.to_string().contains("[possible values: asc, desc]"));
+    }
+}
@@ -10,7 +10,7 @@ use crate::api::{
 use crate::cli::Cli;
 use clap::Parser;
 use dotenvy::dotenv;
-use pexels_api::{MediaSort, MediaType};
+use pexels_api::MediaType;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -55,9 +55,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         cli::Command::SearchMedia { query, per_page, page, r#type, sort } => {
             // Search for media (photos and videos) based on the query
-            let mtype = r#type.parse::<MediaType>()?;
-            let msort = sort.parse::<MediaSort>()?;
-            let media_response = search_media(query, per_page, page, mtype, msort).await?;
+            let media_response =
+                search_media(query, per_page, page, r#type.unwrap_or(MediaType::Empty), sort)
+                    .await?;
             for media in media_response.media {
                 println!("{media:?}");
             }